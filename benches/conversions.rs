@@ -24,6 +24,21 @@ macro_rules! bench_three_generic {
     };
 }
 
+macro_rules! bench_four_generic {
+    ($c: expr, $pc: expr, $f: path, $id:literal, $n:literal, $t:ty, $ts:literal) => {
+        $c.bench_function(concat!($id, "_", $n, $ts), |b| {
+            b.iter(|| {
+                let mut pixels = $pc.clone();
+                black_box(
+                    pixels
+                        .chunks_exact_mut(4)
+                        .for_each(|pixel| $f(pixel.try_into().unwrap())),
+                );
+            })
+        })
+    };
+}
+
 macro_rules! bench_one_generic {
     ($c: expr, $ps: expr, $f: path, $id:literal, $t:ty, $ts:literal) => {
         $c.bench_function(concat!($id, "_", $ts), |b| {
@@ -113,6 +128,15 @@ pub fn conversions(c: &mut Criterion) {
         };
     }
 
+    macro_rules! bench_four {
+        ($f: path, $id:literal) => {
+            bench_four_generic!(c, pix_chunk_3f32, $f, $id, 3, f32, "f32");
+            bench_four_generic!(c, pix_chunk_3f64, $f, $id, 3, f64, "f64");
+            bench_four_generic!(c, pix_chunk_4f32, $f, $id, 4, f32, "f32");
+            bench_four_generic!(c, pix_chunk_4f64, $f, $id, 4, f64, "f64");
+        };
+    }
+
     macro_rules! bench_one {
         ($f: path, $id:literal) => {
             bench_one_generic!(c, pix_slice_3f32, $f, $id, f32, "f32");
@@ -132,8 +156,13 @@ pub fn conversions(c: &mut Criterion) {
     // Forward
     bench_three!(colcon::srgb_to_lrgb, "srgb_to_lrgb");
     bench_three!(colcon::lrgb_to_xyz, "lrgb_to_xyz");
+    bench_four!(colcon::lrgb_to_xyz_x4, "lrgb_to_xyz_x4");
     bench_three!(colcon::xyz_to_cielab, "xyz_to_cielab");
     bench_three!(colcon::xyz_to_oklab, "xyz_to_oklab");
+    bench_three!(colcon::lrgb_to_oklab, "lrgb_to_oklab");
+    bench_three!(colcon::lrgb_to_lp3, "lrgb_to_lp3");
+    bench_three!(colcon::lrgb_to_lrec2020, "lrgb_to_lrec2020");
+    bench_three!(colcon::lp3_to_lrec2020, "lp3_to_lrec2020");
     bench_three!(colcon::xyz_to_jzazbz, "xyz_to_jzazbz");
     bench_three!(colcon::lab_to_lch, "lab_to_lch");
     bench_three!(colcon::srgb_to_hsv, "srgb_to_hsv");
@@ -141,6 +170,10 @@ pub fn conversions(c: &mut Criterion) {
     bench_three!(colcon::lch_to_lab, "lch_to_lab");
     bench_three!(colcon::jzazbz_to_xyz, "jzazbz_to_xyz");
     bench_three!(colcon::oklab_to_xyz, "oklab_to_xyz");
+    bench_three!(colcon::oklab_to_lrgb, "oklab_to_lrgb");
+    bench_three!(colcon::lp3_to_lrgb, "lp3_to_lrgb");
+    bench_three!(colcon::lrec2020_to_lrgb, "lrec2020_to_lrgb");
+    bench_three!(colcon::lrec2020_to_lp3, "lrec2020_to_lp3");
     bench_three!(colcon::cielab_to_xyz, "cielab_to_xyz");
     bench_three!(colcon::xyz_to_lrgb, "xyz_to_lrgb");
     bench_three!(colcon::lrgb_to_srgb, "lrgb_to_srgb");
@@ -148,12 +181,16 @@ pub fn conversions(c: &mut Criterion) {
 
     bench_one!(colcon::srgb_eotf, "srgb_eotf");
     bench_one!(colcon::srgb_oetf, "srgb_oetf");
+    bench_one!(colcon::srgb_eotf_fast, "srgb_eotf_fast");
+    bench_one!(colcon::srgb_oetf_fast, "srgb_oetf_fast");
     bench_one!(colcon::pq_eotf, "pq_eotf");
     bench_one!(colcon::pq_oetf, "pq_oetf");
 
     bench_convert!(Space::SRGB, Space::CIELCH, "full_forward");
     bench_convert!(Space::CIELCH, Space::SRGB, "full_backward");
     bench_convert!(Space::LRGB, Space::XYZ, "minimal");
+    bench_convert!(Space::SRGB, Space::OKLAB, "srgb_to_oklab");
+    bench_convert!(Space::SRGB, Space::XYZ, "srgb_to_xyz");
 }
 
 criterion_group!(benches, conversions);
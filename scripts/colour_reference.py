@@ -8,7 +8,10 @@ def rustprint(id, arr):
         layers += f"    [{a[0]:.8f}, {a[1]:.8f}, {a[2]:.8f}],\n"
     print(f"const {id.upper()}: &'static [[f32; 3]] = &[\n{layers}];")
 
-d65 = colour.xyY_to_XYZ(colour.xy_to_xyY(colour.CCS_ILLUMINANTS["CIE 1931 2 Degree Standard Observer"]["D65"]))
+whites = {
+    name: colour.xyY_to_XYZ(colour.xy_to_xyY(colour.CCS_ILLUMINANTS["CIE 1931 2 Degree Standard Observer"][key]))
+    for (name, key) in [("D65", "D65"), ("D50", "D50"), ("D55", "D55"), ("D75", "D75"), ("STD_A", "A"), ("STD_E", "E")]
+}
 srgb = np.array([
     [0.0, 0.0, 0.0],
     [1.0, 0.0, 0.0],
@@ -36,7 +39,8 @@ bt2020 = colour.RGB_to_RGB(srgb, input_colourspace='sRGB', output_colourspace='I
 ictcp = colour.RGB_to_ICtCp(bt2020, method='ITU-R BT.2100-2 PQ')
 ictcp2 = colour.XYZ_to_ICtCp(xyz, method='ITU-R BT.2100-2 PQ')
 
-print(f"pub const D65: [f32; 3] = [{d65[0]}, {d65[1]}, {d65[2]}];")
+for name, white in whites.items():
+    print(f"pub const {name}: [f32; 3] = [{white[0]}, {white[1]}, {white[2]}];")
 
 print()
 
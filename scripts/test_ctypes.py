@@ -22,6 +22,15 @@ colcon.convert_space_3f32.argtypes = [
 ]
 colcon.convert_space_3f32.restype = ctypes.c_int32
 
+colcon.convert_space_copy_3f32.argtypes = [
+    ctypes.c_char_p,
+    ctypes.c_char_p,
+    c_float_p,
+    c_float_p,
+    ctypes.c_uint,
+]
+colcon.convert_space_copy_3f32.restype = ctypes.c_int32
+
 colcon.str2space_3f32.argtypes = [ctypes.c_char_p, ctypes.c_char_p]
 colcon.str2space_3f32.restype = c_float_p  # No way to have a known size?
 
@@ -56,8 +65,18 @@ colcon.pq_oetf_f32.argtypes = [ctypes.c_float]
 colcon.pq_oetf_f32.restype = ctypes.c_float
 colcon.pqz_oetf_f32.argtypes = [ctypes.c_float]
 colcon.pqz_oetf_f32.restype = ctypes.c_float
+colcon.hlg_oetf_f32.argtypes = [ctypes.c_float]
+colcon.hlg_oetf_f32.restype = ctypes.c_float
+colcon.hlg_eotf_f32.argtypes = [ctypes.c_float]
+colcon.hlg_eotf_f32.restype = ctypes.c_float
+colcon.bt709_oetf_f32.argtypes = [ctypes.c_float]
+colcon.bt709_oetf_f32.restype = ctypes.c_float
+colcon.bt709_eotf_f32.argtypes = [ctypes.c_float]
+colcon.bt709_eotf_f32.restype = ctypes.c_float
 colcon.hk_high2023_3f32.argtypes = [c_float3]
 colcon.hk_high2023_comp_3f32.argtypes = [c_float3]
+colcon.relative_luminance_3f32.argtypes = [c_float3]
+colcon.relative_luminance_3f32.restype = ctypes.c_float
 
 # other dtypes
 colcon.srgb_to_lrgb_4f32.argtypes = [ctypes.c_float * 4]
@@ -143,11 +162,23 @@ pix = c_float3(*SRGB)
 colcon.srgb_to_hsv_3f32(pix)
 pixcmp(list(pix), HSV)
 
+if abs(colcon.relative_luminance_3f32(c_float3(*SRGB)) - XYZ[1]) > 1e-4:
+    print("RELATIVE LUMINANCE FAIL")
+
 pix = (ctypes.c_float * len(SRGB))(*SRGB)
 if colcon.convert_space_3f32("srgb".encode(), "lch".encode(), pix, len(pix)) != 0:
     print("CONVERT SPACE FAIL")
 pixcmp(list(pix), LCH)
 
+src = (ctypes.c_float * len(SRGB))(*SRGB)
+dst = (ctypes.c_float * len(SRGB))()
+if colcon.convert_space_copy_3f32("srgb".encode(), "lch".encode(), src, dst, len(src)) != 0:
+    print("CONVERT SPACE COPY FAIL")
+pixcmp(list(src), SRGB)
+pixcmp(list(dst), LCH)
+if colcon.convert_space_copy_3f32("srgb".encode(), "lch".encode(), src, None, len(src)) != 4:
+    print("CONVERT SPACE COPY NULL FAIL")
+
 pix = colcon.str2space_3f32(f"oklab {OKLAB}".encode(), "srgb".encode())
 pixcmp(pix[0:3], SRGB)
 # validate null is utilized
@@ -50,6 +50,44 @@ impl FromF32 for f64 {
     }
 }
 
+#[allow(missing_docs)]
+/// Convert an F64 into any supported DType.
+/// Used for constants which need more than f32 precision in f64 conversions
+pub trait FromF64: Sized {
+    fn ff64(f: f64) -> Self;
+}
+
+impl FromF64 for f32 {
+    fn ff64(f: f64) -> Self {
+        f as f32
+    }
+}
+
+impl FromF64 for f64 {
+    fn ff64(f: f64) -> Self {
+        f
+    }
+}
+
+#[allow(missing_docs)]
+/// Convert any supported DType into an F64.
+/// Paired with `FromF64` to cast between DTypes without losing precision
+pub trait IntoF64: Sized {
+    fn if64(self) -> f64;
+}
+
+impl IntoF64 for f32 {
+    fn if64(self) -> f64 {
+        self.into()
+    }
+}
+
+impl IntoF64 for f64 {
+    fn if64(self) -> f64 {
+        self
+    }
+}
+
 trait ToDType<T>: Sized {
     fn to_dt(self) -> T;
 }
@@ -63,6 +101,15 @@ where
     }
 }
 
+impl<U> ToDType<U> for f64
+where
+    U: FromF64 + Sized,
+{
+    fn to_dt(self) -> U {
+        FromF64::ff64(self)
+    }
+}
+
 #[allow(missing_docs)]
 /// Trait for all supported data types in colcon
 pub trait DType:
@@ -78,6 +125,8 @@ pub trait DType:
     + Debug
     + Display
     + FromF32
+    + FromF64
+    + IntoF64
 {
     fn powi(self, rhs: i32) -> Self;
     fn powf(self, rhs: Self) -> Self;
@@ -89,12 +138,19 @@ pub trait DType:
     fn trunc(self) -> Self;
     fn max(self, other: Self) -> Self;
     fn min(self, other: Self) -> Self;
+    /// Panics if `min > max` or either bound is NaN, same as the float `clamp`.
+    /// NaN stays NaN, unlike `max(min).min(max)` which sends it to `min`
+    fn clamp(self, min: Self, max: Self) -> Self;
 
     fn sin(self) -> Self;
     fn cos(self) -> Self;
     fn to_degrees(self) -> Self;
     fn to_radians(self) -> Self;
     fn atan2(self, rhs: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn is_nan(self) -> bool;
+    fn is_finite(self) -> bool;
 
     fn sqrt(self) -> Self {
         self.powf((1.0 / 2.0).to_dt())
@@ -119,6 +175,11 @@ pub trait DType:
             self * mul + add
         }
     }
+
+    /// Linear interpolation from `self` at `t = 0` to `other` at `t = 1`, extrapolating outside of that
+    fn lerp(self, other: Self, t: Self) -> Self {
+        (other - self).fma(t, self)
+    }
 }
 
 macro_rules! impl_float {
@@ -148,6 +209,9 @@ macro_rules! impl_float {
             fn min(self, other: Self) -> Self {
                 self.min(other)
             }
+            fn clamp(self, min: Self, max: Self) -> Self {
+                self.clamp(min, max)
+            }
             fn sin(self) -> Self {
                 self.sin()
             }
@@ -163,6 +227,18 @@ macro_rules! impl_float {
             fn atan2(self, rhs: Self) -> Self {
                 self.atan2(rhs)
             }
+            fn exp(self) -> Self {
+                self.exp()
+            }
+            fn ln(self) -> Self {
+                self.ln()
+            }
+            fn is_nan(self) -> bool {
+                self.is_nan()
+            }
+            fn is_finite(self) -> bool {
+                self.is_finite()
+            }
             fn sqrt(self) -> Self {
                 self.sqrt()
             }
@@ -180,6 +256,95 @@ macro_rules! impl_float {
 impl_float!(f32);
 impl_float!(f64);
 
+#[cfg(feature = "half")]
+impl FromF32 for half::f16 {
+    fn ff32(f: f32) -> Self {
+        half::f16::from_f32(f)
+    }
+}
+
+#[cfg(feature = "half")]
+impl FromF64 for half::f16 {
+    fn ff64(f: f64) -> Self {
+        half::f16::from_f64(f)
+    }
+}
+
+#[cfg(feature = "half")]
+impl IntoF64 for half::f16 {
+    fn if64(self) -> f64 {
+        self.to_f64()
+    }
+}
+
+/// Every op runs in f32 and rounds back to f16, as do constants and matrix coefficients. So conversions
+/// are only accurate to a few 1e-3, and an sRGB to OKLAB round trip can drift by 1.5e-2 or about four 8 bit steps.
+/// Just storing the OKLAB in f16 costs under 1e-3 of that, so for accuracy use `convert_space_cast` into f32.
+/// JzAzBz's PQ curve falls apart at this precision, so always convert it in f32.
+#[cfg(feature = "half")]
+impl DType for half::f16 {
+    fn powi(self, rhs: i32) -> Self {
+        Self::from_f32(self.to_f32().powi(rhs))
+    }
+    fn powf(self, rhs: Self) -> Self {
+        Self::from_f32(self.to_f32().powf(rhs.to_f32()))
+    }
+    fn spowf(self, rhs: Self) -> Self {
+        Self::from_f32(self.to_f32().abs().powf(rhs.to_f32()).copysign(self.to_f32()))
+    }
+    fn rem_euclid(self, rhs: Self) -> Self {
+        Self::from_f32(self.to_f32().rem_euclid(rhs.to_f32()))
+    }
+    fn abs(self) -> Self {
+        Self::from_f32(self.to_f32().abs())
+    }
+    fn trunc(self) -> Self {
+        Self::from_f32(self.to_f32().trunc())
+    }
+    fn max(self, other: Self) -> Self {
+        Self::from_f32(self.to_f32().max(other.to_f32()))
+    }
+    fn min(self, other: Self) -> Self {
+        Self::from_f32(self.to_f32().min(other.to_f32()))
+    }
+    fn clamp(self, min: Self, max: Self) -> Self {
+        Self::from_f32(self.to_f32().clamp(min.to_f32(), max.to_f32()))
+    }
+    fn sin(self) -> Self {
+        Self::from_f32(self.to_f32().sin())
+    }
+    fn cos(self) -> Self {
+        Self::from_f32(self.to_f32().cos())
+    }
+    fn to_degrees(self) -> Self {
+        Self::from_f32(self.to_f32().to_degrees())
+    }
+    fn to_radians(self) -> Self {
+        Self::from_f32(self.to_f32().to_radians())
+    }
+    fn atan2(self, rhs: Self) -> Self {
+        Self::from_f32(self.to_f32().atan2(rhs.to_f32()))
+    }
+    fn exp(self) -> Self {
+        Self::from_f32(self.to_f32().exp())
+    }
+    fn ln(self) -> Self {
+        Self::from_f32(self.to_f32().ln())
+    }
+    fn is_nan(self) -> bool {
+        self.is_nan()
+    }
+    fn is_finite(self) -> bool {
+        self.is_finite()
+    }
+    fn sqrt(self) -> Self {
+        Self::from_f32(self.to_f32().sqrt())
+    }
+    fn _fma(self, mul: Self, add: Self) -> Self {
+        Self::from_f32(self.to_f32().mul_add(mul.to_f32(), add.to_f32()))
+    }
+}
+
 // }}}
 
 /// Create an array of separate channel buffers from a single interwoven buffer.
@@ -218,38 +383,141 @@ where
         .into_boxed_slice()
 }
 
+/// Reorder the channels of `pixel` so that channel `n` becomes the previous `pixel[order[n]]`.
+///
+/// # Examples
+///
+/// ```
+/// let mut pixel = [1, 2, 3, 4];
+/// colcon::swizzle(&mut pixel, [3, 0, 1, 2]);
+/// assert_eq!(pixel, [4, 1, 2, 3]);
+/// ```
+pub fn swizzle<T: Copy, const N: usize>(pixel: &mut [T; N], order: [usize; N]) {
+    debug_assert!(order.iter().all(|n| *n < N), "Swizzle order {:?} out of bounds", order);
+    let source = *pixel;
+    pixel.iter_mut().zip(order).for_each(|(c, n)| *c = source[n]);
+}
+
+/// Swap the red and blue channels of an RGBA pixel to produce BGRA.
+pub fn rgba_to_bgra<T: Copy>(pixel: &mut [T; 4]) {
+    swizzle(pixel, [2, 1, 0, 3])
+}
+
+/// Swap the blue and red channels of a BGRA pixel to produce RGBA.
+pub fn bgra_to_rgba<T: Copy>(pixel: &mut [T; 4]) {
+    swizzle(pixel, [2, 1, 0, 3])
+}
+
 // ### CONSTS ### {{{
 
 /// Standard Illuminant D65.
 pub const D65: [f32; 3] = [0.9504559270516716, 1.0, 1.0890577507598784];
 
-const SRGBEOTF_ALPHA: f32 = 0.055;
-const SRGBEOTF_GAMMA: f32 = 2.4;
+/// Standard Illuminant D50.
+pub const D50: [f32; 3] = [0.9642956764295677, 1.0, 0.8251046025104602];
+
+/// Standard Illuminant D55.
+pub const D55: [f32; 3] = [0.9567982961086806, 1.0, 0.9213965001151276];
+
+/// Standard Illuminant D75.
+pub const D75: [f32; 3] = [0.9496633638211383, 1.0, 1.226149644308943];
+
+/// Standard Illuminant A. Incandescent/tungsten.
+pub const STD_A: [f32; 3] = [1.098490612345073, 1.0, 0.35579825745490257];
+
+/// Standard Illuminant E. Equal energy.
+pub const STD_E: [f32; 3] = [1.0, 1.0, 1.0];
+
+/// Standard illuminants for white point dependent conversions.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Illuminant {
+    /// Standard Illuminant D50
+    D50,
+    /// Standard Illuminant D55
+    D55,
+    /// Standard Illuminant D65
+    D65,
+    /// Standard Illuminant D75
+    D75,
+    /// Standard Illuminant A
+    A,
+    /// Standard Illuminant E
+    E,
+}
+
+impl Illuminant {
+    /// CIE XYZ of the illuminant's white point, normalized to Y = 1
+    pub const fn xyz(&self) -> [f32; 3] {
+        match self {
+            Illuminant::D50 => D50,
+            Illuminant::D55 => D55,
+            Illuminant::D65 => D65,
+            Illuminant::D75 => D75,
+            Illuminant::A => STD_A,
+            Illuminant::E => STD_E,
+        }
+    }
+}
+
+const SRGBEOTF_ALPHA: f64 = 0.055;
+const SRGBEOTF_GAMMA: f64 = 2.4;
 // more precise older specs
 // const SRGBEOTF_PHI: f32 = 12.9232102;
 // const SRGBEOTF_CHI: f32 = 0.0392857;
 // const SRGBEOTF_CHI_INV: f32 = 0.0030399;
 // less precise but basically official now
-const SRGBEOTF_PHI: f32 = 12.92;
-const SRGBEOTF_CHI: f32 = 0.04045;
-const SRGBEOTF_CHI_INV: f32 = 0.0031308;
+const SRGBEOTF_PHI: f64 = 12.92;
+const SRGBEOTF_CHI: f64 = 0.04045;
+const SRGBEOTF_CHI_INV: f64 = 0.0031308;
 
 // CIE LAB
 const LAB_DELTA: f32 = 6.0 / 29.0;
 
 // <PQ EOTF Table 4 <https://www.itu.int/rec/R-REC-BT.2100/en>
-const PQEOTF_M1: f32 = 2610. / 16384.;
-const PQEOTF_M2: f32 = 2523. / 4096. * 128.;
-const PQEOTF_C1: f32 = 3424. / 4096.;
-const PQEOTF_C2: f32 = 2413. / 4096. * 32.;
-const PQEOTF_C3: f32 = 2392. / 4096. * 32.;
+const PQEOTF_M1: f64 = 2610. / 16384.;
+const PQEOTF_M2: f64 = 2523. / 4096. * 128.;
+const PQEOTF_C1: f64 = 3424. / 4096.;
+const PQEOTF_C2: f64 = 2413. / 4096. * 32.;
+const PQEOTF_C3: f64 = 2392. / 4096. * 32.;
+
+// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 5 "Hybrid Log-Gamma"
+const HLG_A: f64 = 0.17883277;
+const HLG_B: f64 = 1.0 - 4.0 * HLG_A;
+const HLG_C: f64 = 0.55991073;
+
+// <https://www.itu.int/rec/R-REC-BT.709/en> 1.2 "Opto-electronic conversion characteristics"
+const BT709_ALPHA: f64 = 1.099;
+const BT709_BETA: f64 = 0.018;
+const BT709_GAMMA: f64 = 0.45;
+
+// <https://www.w3.org/TR/css-color-4/#color-conversion-code> CSS keeps the unrounded Rec. 2020 values
+const CSS_REC2020_ALPHA: f64 = 1.09929682680944;
+const CSS_REC2020_BETA: f64 = 0.018053968510807;
+
+// <https://docs.acescentral.com/specifications/acescc/> and <https://docs.acescentral.com/specifications/acescct/>
+const ACESCC_OFFSET: f64 = 9.72;
+const ACESCC_SCALE: f64 = 17.52;
+/// log2 of the largest half float, where both ACES log curves saturate
+const ACESCC_LOG_MAX: f64 = 15.999_295_387_023_41;
+const ACESCCT_X_BRK: f64 = 0.0078125;
+const ACESCCT_Y_BRK: f64 = 0.155_251_141_552_511;
+const ACESCCT_A: f64 = 10.540_237_741_654_5;
+const ACESCCT_B: f64 = 0.072_905_534_195_835_5;
+
+// Oklab L toe <https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab>
+const OKLAB_TOE_K1: f64 = 0.206;
+const OKLAB_TOE_K2: f64 = 0.03;
+const OKLAB_TOE_K3: f64 = (1.0 + OKLAB_TOE_K1) / (1.0 + OKLAB_TOE_K2);
 
 // JzAzBz
-const JZAZBZ_B: f32 = 1.15;
-const JZAZBZ_G: f32 = 0.66;
-const JZAZBZ_D: f32 = -0.56;
-const JZAZBZ_D0: f32 = 1.6295499532821566e-11;
-const JZAZBZ_P: f32 = 1.7 * PQEOTF_M2;
+const JZAZBZ_B: f64 = 1.15;
+const JZAZBZ_G: f64 = 0.66;
+const JZAZBZ_D: f64 = -0.56;
+const JZAZBZ_D0: f64 = 1.6295499532821566e-11;
+const JZAZBZ_P: f64 = 1.7 * PQEOTF_M2;
+
+/// Suggested factor for `jzazbz_scale`, bringing the Jz of diffuse sRGB white from about 0.0176 up to about 1.76.
+pub const JZAZBZ_SCALE: f32 = 100.0;
 
 // ### CONSTS ### }}}
 
@@ -258,7 +526,7 @@ const JZAZBZ_P: f32 = 1.7 * PQEOTF_M2;
 /// Its easier to write matricies visually then transpose them so they can be indexed per vector
 /// [X1, X2] -> [X1, Y1]
 /// [Y1, Y2]    [X2, Y2]
-const fn t(m: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+const fn t<C: Copy>(m: [[C; 3]; 3]) -> [[C; 3]; 3] {
     [
         [m[0][0], m[1][0], m[2][0]],
         [m[0][1], m[1][1], m[2][1]],
@@ -266,8 +534,54 @@ const fn t(m: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
     ]
 }
 
-/// Matrix Multiply
-fn mm<T: DType>(m: [[f32; 3]; 3], p: [T; 3]) -> [T; 3] {
+/// Matrix Multiply usable in `const` contexts. Never fused so may differ from `mm` by an ulp
+const fn mm_const(m: [[f32; 3]; 3], p: [f32; 3]) -> [f32; 3] {
+    [
+        p[0] * m[0][0] + p[1] * m[1][0] + p[2] * m[2][0],
+        p[0] * m[0][1] + p[1] * m[1][1] + p[2] * m[2][1],
+        p[0] * m[0][2] + p[1] * m[1][2] + p[2] * m[2][2],
+    ]
+}
+
+/// Determinant of a 3x3 matrix
+const fn det(m: [[f32; 3]; 3]) -> f32 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Invert a 3x3 matrix using its adjugate.
+/// Works on either layout as inverting commutes with transposing.
+const fn inv(m: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    let d = det(m);
+    [
+        [
+            (m[1][1] * m[2][2] - m[1][2] * m[2][1]) / d,
+            (m[0][2] * m[2][1] - m[0][1] * m[2][2]) / d,
+            (m[0][1] * m[1][2] - m[0][2] * m[1][1]) / d,
+        ],
+        [
+            (m[1][2] * m[2][0] - m[1][0] * m[2][2]) / d,
+            (m[0][0] * m[2][2] - m[0][2] * m[2][0]) / d,
+            (m[0][2] * m[1][0] - m[0][0] * m[1][2]) / d,
+        ],
+        [
+            (m[1][0] * m[2][1] - m[1][1] * m[2][0]) / d,
+            (m[0][1] * m[2][0] - m[0][0] * m[2][1]) / d,
+            (m[0][0] * m[1][1] - m[0][1] * m[1][0]) / d,
+        ],
+    ]
+}
+
+/// Matrix Multiply. Coefficients are f32 except where f64 inputs need more precision than that
+fn mm<T: DType, C: Copy + ToDType<T>>(m: [[C; 3]; 3], p: [T; 3]) -> [T; 3] {
+    if cfg!(feature = "precise") {
+        mm_compensated(m, p)
+    } else {
+        mm_fma(m, p)
+    }
+}
+
+fn mm_fma<T: DType, C: Copy + ToDType<T>>(m: [[C; 3]; 3], p: [T; 3]) -> [T; 3] {
     [
         p[0].fma(m[0][0].to_dt(), p[1].fma(m[1][0].to_dt(), p[2] * m[2][0].to_dt())),
         p[0].fma(m[0][1].to_dt(), p[1].fma(m[1][1].to_dt(), p[2] * m[2][1].to_dt())),
@@ -275,7 +589,43 @@ fn mm<T: DType>(m: [[f32; 3]; 3], p: [T; 3]) -> [T; 3] {
     ]
 }
 
+/// `mm` with error-free products and Neumaier summation, so each channel is off by at most about 1 ulp.
+/// Uses the true fused multiply-add which is slow without hardware support.
+fn mm_compensated<T: DType, C: Copy + ToDType<T>>(m: [[C; 3]; 3], p: [T; 3]) -> [T; 3] {
+    [0, 1, 2].map(|i| {
+        let (mut sum, mut err) = (T::ff32(0.0), T::ff32(0.0));
+        for (x, row) in p.iter().zip(m.iter()) {
+            let coeff: T = row[i].to_dt();
+            let prod = *x * coeff;
+            let t = sum + prod;
+            err = err + x._fma(coeff, -prod);
+            err = err
+                + if sum.abs() >= prod.abs() {
+                    (sum - t) + prod
+                } else {
+                    (prod - t) + sum
+                };
+            sum = t;
+        }
+        sum + err
+    })
+}
+
+/// Matrix Multiply four pixels at once, laid out per channel as `[[R; 4], [G; 4], [B; 4]]`
+/// so each output channel is a plain 4 wide loop the compiler can vectorize
+fn mm4<T: DType>(m: [[f32; 3]; 3], p: [[T; 4]; 3]) -> [[T; 4]; 3] {
+    let mut result = [[T::ff32(0.0); 4]; 3];
+    for (i, out) in result.iter_mut().enumerate() {
+        let (m0, m1, m2) = (m[0][i].to_dt(), m[1][i].to_dt(), m[2][i].to_dt());
+        for n in 0..4 {
+            out[n] = p[0][n].fma(m0, p[1][n].fma(m1, p[2][n] * m2));
+        }
+    }
+    result
+}
+
 // CIE XYZ
+// Rounded to 4 decimals as published in IEC 61966-2-1
 const XYZ65_MAT: [[f32; 3]; 3] = t([
     [0.4124, 0.3576, 0.1805],
     [0.2126, 0.7152, 0.0722],
@@ -296,6 +646,20 @@ const XYZ65_MAT_INV: [[f32; 3]; 3] = t([
     [0.0557101204, -0.2040210506, 1.0569959423],
 ]);
 
+// Full precision matrix derived from the sRGB primaries and D65 chromaticity.
+// Maps LRGB white exactly onto `D65` rather than the rounded IEC 61966-2-1 values
+const XYZ65_MAT_PRECISE: [[f32; 3]; 3] = t([
+    [0.4123907993, 0.3575843394, 0.1804807884],
+    [0.2126390059, 0.7151686788, 0.0721923154],
+    [0.0193308187, 0.1191947798, 0.9505321522],
+]);
+
+const XYZ65_MAT_PRECISE_INV: [[f32; 3]; 3] = t([
+    [3.2409699419, -1.5373831776, -0.4986107603],
+    [-0.9692436363, 1.8759675015, 0.0415550574],
+    [0.0556300797, -0.2039769589, 1.0569715142],
+]);
+
 // OKLAB
 // They appear to be provided already transposed for code in the blog post
 const OKLAB_M1: [[f32; 3]; 3] = [
@@ -319,27 +683,81 @@ const OKLAB_M2_INV: [[f32; 3]; 3] = [
     [0.2158037581, -0.0638541748, -1.2914855379],
 ];
 
+// OKLAB_M1 folded with XYZ65_MAT so LRGB can reach LMS in a single multiply
+const LRGB_TO_LMS: [[f32; 3]; 3] = t([
+    [0.4121738503, 0.5362974607, 0.0514630293],
+    [0.2118721405, 0.6807476834, 0.1074064568],
+    [0.0883154112, 0.2818663071, 0.6302634466],
+]);
+const LMS_TO_LRGB: [[f32; 3]; 3] = t([
+    [4.0767584135, -3.3072279875, 0.2307214601],
+    [-1.2681810851, 2.6092932103, -0.3411121167],
+    [-0.0040984078, -0.7035036601, 1.7068604530],
+]);
+
+// Linear sRGB to linear Display P3, both D65.
+// Derived directly from the primaries so white stays exactly white.
+const LRGB_TO_LP3: [[f32; 3]; 3] = t([
+    [0.8224619687, 0.1775380313, 0.0000000000],
+    [0.0331941989, 0.9668058011, 0.0000000000],
+    [0.0170826307, 0.0723974407, 0.9105199286],
+]);
+// Linear Display P3 to linear sRGB, both D65. Inverse of LRGB_TO_LP3
+const LP3_TO_LRGB: [[f32; 3]; 3] = t([
+    [1.2249401763, -0.2249401763, 0.0000000000],
+    [-0.0420569547, 1.0420569547, 0.0000000000],
+    [-0.0196375546, -0.0786360456, 1.0982736001],
+]);
+
+// Linear sRGB to linear Rec. 2020, both D65. Same as the ITU-R BT.2087 matrix.
+// This and the other Rec. 2020 matrices are folded through CIE XYZ ahead of time
+// so each RGB to RGB conversion costs one multiply instead of two.
+const LRGB_TO_LREC2020: [[f32; 3]; 3] = t([
+    [0.6274038959, 0.3292830384, 0.0433130657],
+    [0.0690972894, 0.9195403951, 0.0113623156],
+    [0.0163914389, 0.0880133079, 0.8955952532],
+]);
+// Linear Rec. 2020 to linear sRGB, both D65. Inverse of LRGB_TO_LREC2020
+const LREC2020_TO_LRGB: [[f32; 3]; 3] = t([
+    [1.6604910021, -0.5876411388, -0.0728498633],
+    [-0.1245504745, 1.1328998971, -0.0083494226],
+    [-0.0181507634, -0.1005788980, 1.1187296614],
+]);
+// Linear Display P3 to linear Rec. 2020, both D65
+const LP3_TO_LREC2020: [[f32; 3]; 3] = t([
+    [0.7538330344, 0.1985973691, 0.0475695966],
+    [0.0457438490, 0.9417772198, 0.0124789312],
+    [-0.0012103404, 0.0176017173, 0.9836086231],
+]);
+// Linear Rec. 2020 to linear Display P3, both D65. Inverse of LP3_TO_LREC2020
+const LREC2020_TO_LP3: [[f32; 3]; 3] = t([
+    [1.3435782526, -0.2821796705, -0.0613985821],
+    [-0.0652974528, 1.0757879158, -0.0104904631],
+    [0.0028217873, -0.0195984945, 1.0167767073],
+]);
+
 // JzAzBz
-const JZAZBZ_M1: [[f32; 3]; 3] = t([
+// Kept as f64 as the huge chroma of out of gamut colors magnifies f32 coefficient rounding past 1e-3
+const JZAZBZ_M1: [[f64; 3]; 3] = t([
     [0.41478972, 0.579999, 0.0146480],
     [-0.2015100, 1.120649, 0.0531008],
     [-0.0166008, 0.264800, 0.6684799],
 ]);
-const JZAZBZ_M2: [[f32; 3]; 3] = t([
+const JZAZBZ_M2: [[f64; 3]; 3] = t([
     [0.500000, 0.500000, 0.000000],
     [3.524000, -4.066708, 0.542708],
     [0.199076, 1.096799, -1.295875],
 ]);
 
-const JZAZBZ_M1_INV: [[f32; 3]; 3] = t([
-    [1.9242264358, -1.0047923126, 0.037651404],
-    [0.3503167621, 0.7264811939, -0.0653844229],
-    [-0.090982811, -0.3127282905, 1.5227665613],
+const JZAZBZ_M1_INV: [[f64; 3]; 3] = t([
+    [1.9242264357876067, -1.0047923125953655, 0.03765140403061801],
+    [0.35031676209499907, 0.7264811939316552, -0.06538442294808502],
+    [-0.09098281098284758, -0.312728290523074, 1.5227665613052606],
 ]);
-const JZAZBZ_M2_INV: [[f32; 3]; 3] = t([
-    [1., 0.1386050433, 0.0580473162],
-    [1., -0.1386050433, -0.0580473162],
-    [1., -0.096019242, -0.8118918961],
+const JZAZBZ_M2_INV: [[f64; 3]; 3] = t([
+    [1.0, 0.1386050432715393, 0.058047316156118876],
+    [1.0, -0.1386050432715393, -0.058047316156118876],
+    [1.0, -0.09601924202631895, -0.811891896056039],
 ]);
 
 // ICtCp
@@ -364,6 +782,72 @@ const ICTCP_M2_INV: [[f32; 3]; 3] = t([
     [1., -0.008609037, -0.111029625],
     [1., 0.5600313357, -0.320627175],
 ]);
+
+// LMS cone responses
+const LMS_HPE: [[f32; 3]; 3] = t([
+    [0.38971, 0.68898, -0.07868],
+    [-0.22981, 1.18340, 0.04641],
+    [0.00000, 0.00000, 1.00000],
+]);
+const LMS_BRADFORD: [[f32; 3]; 3] = t([
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+]);
+const LMS_CAT02: [[f32; 3]; 3] = t([
+    [0.7328, 0.4296, -0.1624],
+    [-0.7036, 1.6975, 0.0061],
+    [0.0030, 0.0136, 0.9834],
+]);
+const LMS_CAT16: [[f32; 3]; 3] = t([
+    [0.401288, 0.650173, -0.051461],
+    [-0.250268, 1.204414, 0.045854],
+    [-0.002079, 0.048952, 0.953127],
+]);
+const LMS_STOCKMAN_SHARPE: [[f32; 3]; 3] = t([
+    [0.210576, 0.855098, -0.0396983],
+    [-0.417076, 1.177260, 0.0786283],
+    [0.0, 0.0, 0.5168350],
+]);
+
+/// CIE XYZ to LMS cone response matrices.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum LmsMatrix {
+    /// Hunt-Pointer-Estevez, normalized to equal energy
+    #[default]
+    HPE,
+    /// Bradford, used by the Bradford chromatic adaptation transform
+    Bradford,
+    /// CIECAM02 chromatic adaptation transform
+    CAT02,
+    /// CAM16 chromatic adaptation transform
+    CAT16,
+    /// Stockman & Sharpe 2000 cone fundamentals
+    StockmanSharpe,
+}
+
+impl LmsMatrix {
+    const fn m(&self) -> [[f32; 3]; 3] {
+        match self {
+            LmsMatrix::HPE => LMS_HPE,
+            LmsMatrix::Bradford => LMS_BRADFORD,
+            LmsMatrix::CAT02 => LMS_CAT02,
+            LmsMatrix::CAT16 => LMS_CAT16,
+            LmsMatrix::StockmanSharpe => LMS_STOCKMAN_SHARPE,
+        }
+    }
+
+    const fn m_inv(&self) -> [[f32; 3]; 3] {
+        // Resolved at compile time for each variant
+        match self {
+            LmsMatrix::HPE => const { inv(LMS_HPE) },
+            LmsMatrix::Bradford => const { inv(LMS_BRADFORD) },
+            LmsMatrix::CAT02 => const { inv(LMS_CAT02) },
+            LmsMatrix::CAT16 => const { inv(LMS_CAT16) },
+            LmsMatrix::StockmanSharpe => const { inv(LMS_STOCKMAN_SHARPE) },
+        }
+    }
+}
 // ### MATRICES ### }}}
 
 // ### TRANSFER FUNCTIONS ### {{{
@@ -372,10 +856,10 @@ const ICTCP_M2_INV: [[f32; 3]; 3] = t([
 ///
 /// <https://en.wikipedia.org/wiki/SRGB#Computing_the_transfer_function>
 pub fn srgb_eotf<T: DType>(n: T) -> T {
-    if n <= SRGBEOTF_CHI.to_dt() {
-        n / SRGBEOTF_PHI.to_dt()
+    if n <= T::ff64(SRGBEOTF_CHI) {
+        n / T::ff64(SRGBEOTF_PHI)
     } else {
-        ((n + SRGBEOTF_ALPHA.to_dt()) / (SRGBEOTF_ALPHA + 1.0).to_dt()).powf(SRGBEOTF_GAMMA.to_dt())
+        ((n + T::ff64(SRGBEOTF_ALPHA)) / T::ff64(SRGBEOTF_ALPHA + 1.0)).powf(T::ff64(SRGBEOTF_GAMMA))
     }
 }
 
@@ -383,10 +867,44 @@ pub fn srgb_eotf<T: DType>(n: T) -> T {
 ///
 /// <https://en.wikipedia.org/wiki/SRGB#Computing_the_transfer_function>
 pub fn srgb_oetf<T: DType>(n: T) -> T {
-    if n <= SRGBEOTF_CHI_INV.to_dt() {
-        n * SRGBEOTF_PHI.to_dt()
+    if n <= T::ff64(SRGBEOTF_CHI_INV) {
+        n * T::ff64(SRGBEOTF_PHI)
+    } else {
+        (n.powf(T::ff64(1.0 / SRGBEOTF_GAMMA))).fma(T::ff64(1.0 + SRGBEOTF_ALPHA), T::ff64(-SRGBEOTF_ALPHA))
+    }
+}
+
+/// Approximate sRGB Electro-Optical Transfer Function
+///
+/// Replaces the `powf` segment of `srgb_eotf` with a cubic polynomial.
+/// Max error is under 0.5 / 255 for inputs within 0.0..=1.0, diverging outside of that.
+///
+/// <https://chilliant.com/rgb2hsv.html>
+pub fn srgb_eotf_fast<T: DType>(n: T) -> T {
+    if n <= T::ff64(SRGBEOTF_CHI) {
+        n / T::ff64(SRGBEOTF_PHI)
+    } else {
+        n * n.fma(n.fma(0.305306011.to_dt(), 0.682171111.to_dt()), 0.012522878.to_dt())
+    }
+}
+
+/// Approximate inverse sRGB Electro-Optical Transfer Function
+///
+/// Replaces the `powf` segment of `srgb_oetf` with a weighted sum of repeated square roots.
+/// Max error is under 0.5 / 255 for inputs within 0.0..=1.0, diverging outside of that.
+///
+/// <https://chilliant.com/rgb2hsv.html>
+pub fn srgb_oetf_fast<T: DType>(n: T) -> T {
+    if n <= T::ff64(SRGBEOTF_CHI_INV) {
+        n * T::ff64(SRGBEOTF_PHI)
     } else {
-        (n.powf((1.0 / SRGBEOTF_GAMMA).to_dt())).fma((1.0 + SRGBEOTF_ALPHA).to_dt(), (-SRGBEOTF_ALPHA).to_dt())
+        let s1 = n.sqrt();
+        let s2 = s1.sqrt();
+        let s3 = s2.sqrt();
+        T::ff32(0.662002687).fma(
+            s1,
+            T::ff32(0.684122060).fma(s2, T::ff32(-0.323583601).fma(s3, T::ff32(-0.0225411470) * n)),
+        )
     }
 }
 
@@ -394,10 +912,10 @@ pub fn srgb_oetf<T: DType>(n: T) -> T {
 fn pq_eotf_common<T: DType>(e: T, m2: T) -> T {
     let ep_pow_1divm2 = e.spowf(T::ff32(1.0) / m2);
 
-    let numerator: T = (ep_pow_1divm2 - PQEOTF_C1.to_dt()).max(0.0.to_dt());
-    let denominator: T = ep_pow_1divm2.fma(T::ff32(-PQEOTF_C3), PQEOTF_C2.to_dt());
+    let numerator: T = (ep_pow_1divm2 - T::ff64(PQEOTF_C1)).max(0.0.to_dt());
+    let denominator: T = ep_pow_1divm2.fma(T::ff64(-PQEOTF_C3), T::ff64(PQEOTF_C2));
 
-    let y = (numerator / denominator).spowf((1.0 / PQEOTF_M1).to_dt());
+    let y = (numerator / denominator).spowf(T::ff64(1.0 / PQEOTF_M1));
 
     y * 10000.0.to_dt()
 }
@@ -405,10 +923,10 @@ fn pq_eotf_common<T: DType>(e: T, m2: T) -> T {
 // <https://www.itu.int/rec/R-REC-BT.2100/en> Table 4 "Reference PQ OETF"
 fn pq_oetf_common<T: DType>(f: T, m2: T) -> T {
     let y = f / 10000.0.to_dt();
-    let y_pow_m1 = y.spowf(PQEOTF_M1.to_dt());
+    let y_pow_m1 = y.spowf(T::ff64(PQEOTF_M1));
 
-    let numerator: T = T::ff32(PQEOTF_C2).fma(y_pow_m1, PQEOTF_C1.to_dt());
-    let denominator: T = T::ff32(PQEOTF_C3).fma(y_pow_m1, 1.0.to_dt());
+    let numerator: T = T::ff64(PQEOTF_C2).fma(y_pow_m1, T::ff64(PQEOTF_C1));
+    let denominator: T = T::ff64(PQEOTF_C3).fma(y_pow_m1, 1.0.to_dt());
 
     (numerator / denominator).spowf(m2)
 }
@@ -417,14 +935,14 @@ fn pq_oetf_common<T: DType>(f: T, m2: T) -> T {
 ///
 /// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 4 "Reference PQ EOTF"
 pub fn pq_eotf<T: DType>(e: T) -> T {
-    pq_eotf_common(e, PQEOTF_M2.to_dt())
+    pq_eotf_common(e, T::ff64(PQEOTF_M2))
 }
 
 /// Dolby Perceptual Quantizer Optical-Electro Transfer Function primarily used for ICtCP
 ///
 /// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 4 "Reference PQ OETF"
 pub fn pq_oetf<T: DType>(f: T) -> T {
-    pq_oetf_common(f, PQEOTF_M2.to_dt())
+    pq_oetf_common(f, T::ff64(PQEOTF_M2))
 }
 
 /// Dolby Perceptual Quantizer Electro-Optical Transfer Function modified for JzAzBz
@@ -433,7 +951,7 @@ pub fn pq_oetf<T: DType>(f: T) -> T {
 ///
 /// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 4 "Reference PQ EOTF"
 pub fn pqz_eotf<T: DType>(e: T) -> T {
-    pq_eotf_common(e, JZAZBZ_P.to_dt())
+    pq_eotf_common(e, T::ff64(JZAZBZ_P))
 }
 
 /// Dolby Perceptual Quantizer Optical-Electro Transfer Function modified for JzAzBz
@@ -442,15 +960,174 @@ pub fn pqz_eotf<T: DType>(e: T) -> T {
 ///
 /// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 4 "Reference PQ OETF"
 pub fn pqz_oetf<T: DType>(f: T) -> T {
-    pq_oetf_common(f, JZAZBZ_P.to_dt())
+    pq_oetf_common(f, T::ff64(JZAZBZ_P))
+}
+
+/// Hybrid Log-Gamma Opto-Electronic Transfer Function, from scene linear 0.0..=1.0 to signal
+///
+/// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 5 "Hybrid Log-Gamma"
+pub fn hlg_oetf<T: DType>(e: T) -> T {
+    if e <= T::ff64(1.0 / 12.0) {
+        (e * 3.0.to_dt()).sqrt()
+    } else {
+        T::ff64(HLG_A).fma((e * 12.0.to_dt() - T::ff64(HLG_B)).ln(), T::ff64(HLG_C))
+    }
+}
+
+/// Inverse of `hlg_oetf`, from signal back to scene linear without the HLG OOTF
+///
+/// <https://www.itu.int/rec/R-REC-BT.2100/en> Table 5 "Hybrid Log-Gamma"
+pub fn hlg_eotf<T: DType>(e: T) -> T {
+    if e <= 0.5.to_dt() {
+        e * e / 3.0.to_dt()
+    } else {
+        (((e - T::ff64(HLG_C)) / T::ff64(HLG_A)).exp() + T::ff64(HLG_B)) / 12.0.to_dt()
+    }
+}
+
+/// ITU-R BT.709 Opto-Electronic Transfer Function
+///
+/// <https://www.itu.int/rec/R-REC-BT.709/en>
+pub fn bt709_oetf<T: DType>(n: T) -> T {
+    if n < T::ff64(BT709_BETA) {
+        n * 4.5.to_dt()
+    } else {
+        n.powf(T::ff64(BT709_GAMMA))
+            .fma(T::ff64(BT709_ALPHA), T::ff64(1.0 - BT709_ALPHA))
+    }
+}
+
+/// Inverse of `bt709_oetf`. Not the BT.1886 display EOTF
+///
+/// <https://www.itu.int/rec/R-REC-BT.709/en>
+pub fn bt709_eotf<T: DType>(n: T) -> T {
+    if n < T::ff64(BT709_BETA * 4.5) {
+        n / 4.5.to_dt()
+    } else {
+        ((n + T::ff64(BT709_ALPHA - 1.0)) / T::ff64(BT709_ALPHA)).powf(T::ff64(1.0 / BT709_GAMMA))
+    }
+}
+
+fn aces_log_encode<T: DType>(n: T) -> T {
+    (n.ln() / T::ff64(core::f64::consts::LN_2) + T::ff64(ACESCC_OFFSET)) / T::ff64(ACESCC_SCALE)
+}
+
+fn aces_log_decode<T: DType>(n: T) -> T {
+    if n >= T::ff64((ACESCC_LOG_MAX + ACESCC_OFFSET) / ACESCC_SCALE) {
+        T::ff64(65504.0)
+    } else {
+        (n.fma(T::ff64(ACESCC_SCALE), T::ff64(-ACESCC_OFFSET)) * T::ff64(core::f64::consts::LN_2)).exp()
+    }
+}
+
+/// ACEScc logarithmic encoding of ACEScg linear values for grading.
+/// Values at or below zero all encode to the floor of about -0.3584.
+///
+/// <https://docs.acescentral.com/specifications/acescc/>
+pub fn acescc_encode<T: DType>(n: T) -> T {
+    let floor = T::ff64(2f64.powi(-16));
+    if n <= 0.0.to_dt() {
+        aces_log_encode(floor)
+    } else if n < T::ff64(2f64.powi(-15)) {
+        aces_log_encode(n.fma(0.5.to_dt(), floor))
+    } else {
+        aces_log_encode(n)
+    }
+}
+
+/// Inverse of `acescc_encode`, saturating at the f16 maximum of 65504.
+///
+/// <https://docs.acescentral.com/specifications/acescc/>
+pub fn acescc_decode<T: DType>(n: T) -> T {
+    if n <= T::ff64((ACESCC_OFFSET - 15.0) / ACESCC_SCALE) {
+        (aces_log_decode(n) - T::ff64(2f64.powi(-16))) * 2.0.to_dt()
+    } else {
+        aces_log_decode(n)
+    }
+}
+
+/// ACEScct encoding of ACEScg linear values. Same as `acescc_encode` above 0.0078125,
+/// with a linear toe below that so shadows grade more like a film scan.
+///
+/// <https://docs.acescentral.com/specifications/acescct/>
+pub fn acescct_encode<T: DType>(n: T) -> T {
+    if n <= T::ff64(ACESCCT_X_BRK) {
+        n.fma(T::ff64(ACESCCT_A), T::ff64(ACESCCT_B))
+    } else {
+        aces_log_encode(n)
+    }
+}
+
+/// Inverse of `acescct_encode`, saturating at the f16 maximum of 65504.
+///
+/// <https://docs.acescentral.com/specifications/acescct/>
+pub fn acescct_decode<T: DType>(n: T) -> T {
+    if n <= T::ff64(ACESCCT_Y_BRK) {
+        (n - T::ff64(ACESCCT_B)) / T::ff64(ACESCCT_A)
+    } else {
+        aces_log_decode(n)
+    }
+}
+
+/// Transfer functions between linear light and an encoded signal, for composing custom RGB spaces.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TransferFunction {
+    /// `srgb_oetf` and `srgb_eotf`
+    Srgb,
+    /// `pq_oetf` and `pq_eotf`, linear values in absolute nits
+    Pq,
+    /// `hlg_oetf` and `hlg_eotf`
+    Hlg,
+    /// `bt709_oetf` and `bt709_eotf`
+    Bt709,
+    /// `acescc_encode` and `acescc_decode`, linear values in ACEScg
+    AcesCc,
+    /// `acescct_encode` and `acescct_decode`, linear values in ACEScg
+    AcesCct,
+    /// Pure power function with the given gamma
+    Gamma(f32),
+    /// No transfer
+    Linear,
+}
+
+impl TransferFunction {
+    /// Encode linear light into a signal, the OETF
+    pub fn apply<T: DType>(&self, n: T) -> T {
+        match self {
+            TransferFunction::Srgb => srgb_oetf(n),
+            TransferFunction::Pq => pq_oetf(n),
+            TransferFunction::Hlg => hlg_oetf(n),
+            TransferFunction::Bt709 => bt709_oetf(n),
+            TransferFunction::AcesCc => acescc_encode(n),
+            TransferFunction::AcesCct => acescct_encode(n),
+            TransferFunction::Gamma(g) => n.spowf(T::ff32(1.0) / g.to_dt()),
+            TransferFunction::Linear => n,
+        }
+    }
+
+    /// Decode a signal back into linear light, the EOTF
+    pub fn invert<T: DType>(&self, n: T) -> T {
+        match self {
+            TransferFunction::Srgb => srgb_eotf(n),
+            TransferFunction::Pq => pq_eotf(n),
+            TransferFunction::Hlg => hlg_eotf(n),
+            TransferFunction::Bt709 => bt709_eotf(n),
+            TransferFunction::AcesCc => acescc_decode(n),
+            TransferFunction::AcesCct => acescct_decode(n),
+            TransferFunction::Gamma(g) => n.spowf(g.to_dt()),
+            TransferFunction::Linear => n,
+        }
+    }
 }
 
 // ### TRANSFER FUNCTIONS ### }}}
 
 // ### Helmholtz-Kohlrausch ### {{{
 
-/// Extended K-values from High et al 2021/2022
-const K_HIGH2022: [f32; 4] = [0.1644, 0.0603, 0.1307, 0.0060];
+/// Extended K-values from High et al 2021/2022.
+///
+/// `[k1, k2]` scale and offset `hk_2023_fby`, `[k3, k4]` scale and offset `hk_2023_fr`.
+pub const K_HIGH2022: [f32; 4] = [0.1644, 0.0603, 0.1307, 0.0060];
 
 /// Mean value of the HK delta for CIE LCH(ab), High et al 2023 implementation.
 ///
@@ -458,24 +1135,30 @@ const K_HIGH2022: [f32; 4] = [0.1644, 0.0603, 0.1307, 0.0060];
 /// Cannot make a const fn: <https://github.com/rust-lang/rust/issues/57241>
 pub const HIGH2023_MEAN: f32 = 20.956442;
 
+/// Blue-yellow term of `hk_high2023` for a CIE LCH(ab) hue in degrees, applied to every hue.
+pub fn hk_2023_fby<T: DType>(hue: T) -> T {
+    T::ff32(K_HIGH2022[0]).fma(
+        ((hue - 90.0.to_dt()) / 2.0.to_dt()).to_radians().sin().abs(),
+        K_HIGH2022[1].to_dt(),
+    )
+}
+
+/// Red term of `hk_high2023` for a CIE LCH(ab) hue in degrees, zero for hues between 90 and 270.
+pub fn hk_2023_fr<T: DType>(hue: T) -> T {
+    if hue <= 90.0.to_dt() || hue >= 270.0.to_dt() {
+        T::ff32(K_HIGH2022[2]).fma(hue.to_radians().cos().abs(), K_HIGH2022[3].to_dt())
+    } else {
+        0.0.to_dt()
+    }
+}
+
 /// Returns difference in perceptual lightness based on hue, aka the Helmholtz-Kohlrausch effect.
 /// High et al 2023 implementation.
 pub fn hk_high2023<T: DType, const N: usize>(lch: &[T; N]) -> T
 where
     Channels<N>: ValidChannels,
 {
-    let fby: T = T::ff32(K_HIGH2022[0]).fma(
-        ((lch[2] - 90.0.to_dt()) / 2.0.to_dt()).to_radians().sin().abs(),
-        K_HIGH2022[1].to_dt(),
-    );
-
-    let fr: T = if lch[2] <= 90.0.to_dt() || lch[2] >= 270.0.to_dt() {
-        T::ff32(K_HIGH2022[2]).fma(lch[2].to_radians().cos().abs(), K_HIGH2022[3].to_dt())
-    } else {
-        0.0.to_dt()
-    };
-
-    (fby + fr) * lch[1]
+    (hk_2023_fby(lch[2]) + hk_2023_fr(lch[2])) * lch[1]
 }
 
 /// Compensates CIE LCH's L value for the Helmholtz-Kohlrausch effect.
@@ -487,12 +1170,1317 @@ where
     lch[0] = lch[0] + (T::ff32(HIGH2023_MEAN) - hk_high2023(lch)) * (lch[1] / 100.0.to_dt())
 }
 
+/// `hk_high2023_comp` over a whole buffer of CIE LCH(ab) pixels.
+pub fn hk_high2023_comp_slice<T: DType, const N: usize>(pixels: &mut [[T; N]])
+where
+    Channels<N>: ValidChannels,
+{
+    pixels.iter_mut().for_each(|pixel| hk_high2023_comp(pixel))
+}
+
+/// Same as `hk_high2023_comp_slice` but with FFI types, ignoring remainder values like `convert_space_sliced`.
+///
+/// Returns 0 on success, 3 on invalid `pixels`
+///
+/// `len` is in elements rather than bytes
+///
+/// # Safety
+/// `pixels` must be null or valid for reads and writes of `len` elements.
+pub unsafe fn hk_high2023_comp_ffi<T: DType, const N: usize>(pixels: *mut T, len: usize) -> i32
+where
+    Channels<N>: ValidChannels,
+{
+    if pixels.is_null() {
+        return 3;
+    }
+    let pixels = unsafe { core::slice::from_raw_parts_mut(pixels.cast::<[T; N]>(), len / N) };
+    hk_high2023_comp_slice(pixels);
+    0
+}
+
 // ### Helmholtz-Kohlrausch ### }}}
 
-// ### Space ### {{{
+// ### Blackbody ### {{{
 
-/// Defines colorspace pixels will take.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+fn horner<T: DType>(x: T, coeffs: [f32; 4]) -> T {
+    x.fma(
+        x.fma(x.fma(coeffs[0].to_dt(), coeffs[1].to_dt()), coeffs[2].to_dt()),
+        coeffs[3].to_dt(),
+    )
+}
+
+/// Approximate CIE XYZ of a Planckian radiator at `kelvin`, normalized to Y = 1.
+///
+/// Uses the Kim et al. 2002 cubic spline fit of the Planckian locus.
+/// Only valid from 1667K to 25000K and will clamp temperatures outside of that.
+///
+/// <https://en.wikipedia.org/wiki/Planckian_locus#Approximation>
+pub fn blackbody_xyz<T: DType>(kelvin: T) -> [T; 3] {
+    let kelvin = kelvin.max(1667.0.to_dt()).min(25000.0.to_dt());
+    let u = T::ff32(1000.0) / kelvin;
+
+    let x = if kelvin <= 4000.0.to_dt() {
+        horner(u, [-0.2661239, -0.2343589, 0.8776956, 0.179910])
+    } else {
+        horner(u, [-3.0258469, 2.1070379, 0.2226347, 0.240390])
+    };
+
+    let y = if kelvin <= 2222.0.to_dt() {
+        horner(x, [-1.1063814, -1.34811020, 2.18555832, -0.20219683])
+    } else if kelvin <= 4000.0.to_dt() {
+        horner(x, [-0.9549476, -1.37418593, 2.09137015, -0.16748867])
+    } else {
+        horner(x, [3.0817580, -5.87338670, 3.75112997, -0.37001483])
+    };
+
+    [x / y, 1.0.to_dt(), (T::ff32(1.0) - x - y) / y]
+}
+
+/// sRGB color of a Planckian radiator at `kelvin`, for use as a white point or color temperature picker.
+///
+/// Scaled so the brightest channel is 1.0 then clipped,
+/// as the reddest temperatures fall outside of the sRGB gamut.
+pub fn white_point_srgb<T: DType>(kelvin: T) -> [T; 3] {
+    let mut pixel = blackbody_xyz(kelvin);
+    xyz_to_lrgb(&mut pixel);
+    let max = pixel[0].max(pixel[1]).max(pixel[2]);
+    pixel
+        .iter_mut()
+        .for_each(|c| *c = (*c / max).clamp(0.0.to_dt(), 1.0.to_dt()));
+    lrgb_to_srgb(&mut pixel);
+    pixel
+}
+
+/// Warms or cools an sRGB color as if the display's white were swapped for a Planckian radiator at `kelvin`,
+/// like f.lux or night mode.
+///
+/// Linear RGB is multiplied per channel by the radiator's white relative to one at 6500K,
+/// scaled so the brightest channel is 1.0 and in gamut colors stay in gamut.
+/// Using the 6500K radiator instead of D65 itself, which sits slightly off the Planckian locus,
+/// keeps 6500K an identity transform rather than a faint magenta tint.
+pub fn apply_warmth<T: DType>(srgb: &mut [T; 3], kelvin: T) {
+    let lrgb_white = |k: T| {
+        let mut white = blackbody_xyz(k);
+        xyz_to_lrgb::<T, 3>(&mut white);
+        white
+    };
+    let (target, reference) = (lrgb_white(kelvin), lrgb_white(6500.0.to_dt()));
+    let gains = [0, 1, 2].map(|n| (target[n] / reference[n]).max(0.0.to_dt()));
+    let max = gains[0].max(gains[1]).max(gains[2]);
+    srgb_to_lrgb::<T, 3>(srgb);
+    srgb.iter_mut().zip(gains).for_each(|(c, g)| *c = *c * g / max);
+    lrgb_to_srgb::<T, 3>(srgb);
+}
+
+// ### Blackbody ### }}}
+
+// ### CAM16 ### {{{
+
+// Default viewing conditions, precomputed for a D65 white with Y = 100,
+// adapting luminance of 64 lux / pi * 0.2, 20% background, average surround
+const CAM16_FL: f32 = 0.27313053667320736;
+const CAM16_FL_4RT: f32 = 0.7229238694926879;
+const CAM16_CZ: f32 = 0.69 * 1.9272135954999579;
+const CAM16_NBB: f32 = 1.0003040045593807;
+const CAM16_D_RGB: [f32; 3] = [1.0208562217209205, 0.9865141521462043, 0.9348569172102289];
+const CAM16_AW: f32 = 25.518496218771617;
+const CAM16_C_FACTOR: f32 = 0.8952178848134279;
+
+/// CAM16 lightness J, colorfulness M, and hue angle h of a CIE XYZ color.
+///
+/// Uses fixed viewing conditions: D65 white, an adapting luminance of 64 lux / pi * 0.2,
+/// a 20% background and average surround. Only the forward model is provided.
+///
+/// <https://doi.org/10.1002/col.22131>
+pub fn cam16_jmh<T: DType>(xyz: &[T; 3]) -> [T; 3] {
+    let mut rgb = mm(LMS_CAT16, xyz.map(|c| c * 100.0.to_dt()));
+    rgb.iter_mut().zip(CAM16_D_RGB).for_each(|(c, d)| {
+        let f = (*c * T::ff32(d * CAM16_FL / 100.0)).spowf(0.42.to_dt());
+        *c = T::ff32(400.0) * f / (f.abs() + 27.13.to_dt()) + 0.1.to_dt();
+    });
+    let [r, g, b] = rgb;
+
+    let a = r - T::ff32(12.0 / 11.0) * g + b / 11.0.to_dt();
+    let b2 = (r + g - T::ff32(2.0) * b) / 9.0.to_dt();
+    let h = b2.atan2(a).to_degrees().rem_euclid(360.0.to_dt());
+    let et = T::ff32(0.25) * ((h.to_radians() + 2.0.to_dt()).cos() + 3.8.to_dt());
+
+    let achromatic = (T::ff32(2.0) * r + g + b / 20.0.to_dt() - 0.305.to_dt()) * CAM16_NBB.to_dt();
+    let j = T::ff32(100.0) * (achromatic / CAM16_AW.to_dt()).spowf(CAM16_CZ.to_dt());
+
+    let t = T::ff32(50000.0 / 13.0 * CAM16_NBB) * et * a.fma(a, b2 * b2).sqrt() / (r + g + T::ff32(21.0 / 20.0) * b);
+    let m = t.powf(0.9.to_dt()) * (j / 100.0.to_dt()).sqrt() * T::ff32(CAM16_C_FACTOR * CAM16_FL_4RT);
+
+    [j, m, h]
+}
+
+// ### CAM16 ### }}}
+
+// ### Delta E ### {{{
+
+/// CIE 1976 color difference, the Euclidean distance between two CIE LAB colors.
+///
+/// <https://en.wikipedia.org/wiki/Color_difference#CIE76>
+pub fn delta_e_76<T: DType>(lab1: &[T; 3], lab2: &[T; 3]) -> T {
+    lab1.iter()
+        .zip(lab2.iter())
+        .fold(T::ff32(0.0), |acc, (a, b)| acc + (*a - *b).powi(2))
+        .sqrt()
+}
+
+/// Euclidean distance between two OKLAB colors.
+///
+/// <https://www.w3.org/TR/css-color-4/#color-difference-OK>
+pub fn delta_e_ok<T: DType>(lab1: &[T; 3], lab2: &[T; 3]) -> T {
+    delta_e_76(lab1, lab2)
+}
+
+/// CIEDE2000 color difference between two CIE LAB colors, with all weighting factors at unity.
+///
+/// <https://hajim.rochester.edu/ece/sites/gsharma/ciede2000/ciede2000noteCRNA.pdf>
+pub fn delta_e_2000<T: DType>(lab1: &[T; 3], lab2: &[T; 3]) -> T {
+    let [l1, a1, b1] = *lab1;
+    let [l2, a2, b2] = *lab2;
+    let pow25_7 = T::ff32(25.0).powi(7);
+
+    let c_mean = ((a1.powi(2) + b1.powi(2)).sqrt() + (a2.powi(2) + b2.powi(2)).sqrt()) / 2.0.to_dt();
+    let g = T::ff32(0.5) * (T::ff32(1.0) - (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt());
+    let (a1, a2) = (a1 * (g + 1.0.to_dt()), a2 * (g + 1.0.to_dt()));
+
+    let (c1, c2) = ((a1.powi(2) + b1.powi(2)).sqrt(), (a2.powi(2) + b2.powi(2)).sqrt());
+    let hue = |a: T, b: T| {
+        if a == 0.0.to_dt() && b == 0.0.to_dt() {
+            T::ff32(0.0)
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0.to_dt())
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+    let achromatic = c1 * c2 == 0.0.to_dt();
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if achromatic {
+        T::ff32(0.0)
+    } else if h2 - h1 > 180.0.to_dt() {
+        h2 - h1 - 360.0.to_dt()
+    } else if h2 - h1 < (-180.0).to_dt() {
+        h2 - h1 + 360.0.to_dt()
+    } else {
+        h2 - h1
+    };
+    let dh = T::ff32(2.0) * (c1 * c2).sqrt() * (dh / 2.0.to_dt()).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.0.to_dt();
+    let c_mean = (c1 + c2) / 2.0.to_dt();
+    let h_mean = if achromatic {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180.0.to_dt() {
+        (h1 + h2) / 2.0.to_dt()
+    } else if h1 + h2 < 360.0.to_dt() {
+        (h1 + h2 + 360.0.to_dt()) / 2.0.to_dt()
+    } else {
+        (h1 + h2 - 360.0.to_dt()) / 2.0.to_dt()
+    };
+
+    let t = T::ff32(1.0) - T::ff32(0.17) * (h_mean - 30.0.to_dt()).to_radians().cos()
+        + T::ff32(0.24) * (h_mean * 2.0.to_dt()).to_radians().cos()
+        + T::ff32(0.32) * (h_mean * 3.0.to_dt() + 6.0.to_dt()).to_radians().cos()
+        - T::ff32(0.20) * (h_mean * 4.0.to_dt() - 63.0.to_dt()).to_radians().cos();
+    let d_theta = T::ff32(30.0) * (-((h_mean - 275.0.to_dt()) / 25.0.to_dt()).powi(2)).exp();
+    let rc = T::ff32(2.0) * (c_mean.powi(7) / (c_mean.powi(7) + pow25_7)).sqrt();
+    let sl = T::ff32(1.0)
+        + T::ff32(0.015) * (l_mean - 50.0.to_dt()).powi(2) / (T::ff32(20.0) + (l_mean - 50.0.to_dt()).powi(2)).sqrt();
+    let sc = T::ff32(0.045).fma(c_mean, 1.0.to_dt());
+    let sh = (T::ff32(0.015) * c_mean).fma(t, 1.0.to_dt());
+    let rt = -(d_theta * 2.0.to_dt()).to_radians().sin() * rc;
+
+    let [dl, dc, dh] = [dl / sl, dc / sc, dh / sh];
+    (dl.powi(2) + dc.powi(2) + dh.powi(2) + rt * dc * dh).sqrt()
+}
+
+/// CMC l:c color difference between two CIE LAB colors, with `lab1` as the reference.
+///
+/// Common ratios are 2:1 for acceptability and 1:1 for perceptibility. Not symmetric,
+/// as the weighting functions are computed from the reference only.
+///
+/// <http://www.brucelindbloom.com/index.html?Eqn_DeltaE_CMC.html>
+pub fn delta_e_cmc<T: DType>(lab1: &[T; 3], lab2: &[T; 3], l: T, c: T) -> T {
+    let [l1, a1, b1] = *lab1;
+    let [l2, a2, b2] = *lab2;
+    let (c1, c2) = ((a1.powi(2) + b1.powi(2)).sqrt(), (a2.powi(2) + b2.powi(2)).sqrt());
+
+    let dl = l1 - l2;
+    let dc = c1 - c2;
+    let dh2 = ((a1 - a2).powi(2) + (b1 - b2).powi(2) - dc.powi(2)).max(0.0.to_dt());
+
+    let h1 = b1.atan2(a1).to_degrees().rem_euclid(360.0.to_dt());
+    let f = (c1.powi(4) / (c1.powi(4) + 1900.0.to_dt())).sqrt();
+    let t = if h1 >= 164.0.to_dt() && h1 <= 345.0.to_dt() {
+        T::ff64(0.56) + (T::ff64(0.2) * (h1 + 168.0.to_dt()).to_radians().cos()).abs()
+    } else {
+        T::ff64(0.36) + (T::ff64(0.4) * (h1 + 35.0.to_dt()).to_radians().cos()).abs()
+    };
+    let sl = if l1 < 16.0.to_dt() {
+        T::ff64(0.511)
+    } else {
+        T::ff64(0.040975) * l1 / T::ff64(0.01765).fma(l1, 1.0.to_dt())
+    };
+    let sc = T::ff64(0.0638) * c1 / T::ff64(0.0131).fma(c1, 1.0.to_dt()) + T::ff64(0.638);
+    let sh = sc * (f * t + T::ff32(1.0) - f);
+
+    ((dl / (l * sl)).powi(2) + (dc / (c * sc)).powi(2) + dh2 / sh.powi(2)).sqrt()
+}
+
+fn delta_e_slice<T: DType>(a: &[[T; 3]], b: &[[T; 3]], out: &mut [T], f: fn(&[T; 3], &[T; 3]) -> T) {
+    debug_assert!(
+        a.len() == b.len() && a.len() == out.len(),
+        "Delta E slice lengths differ: {} {} {}",
+        a.len(),
+        b.len(),
+        out.len()
+    );
+    a.iter()
+        .zip(b.iter())
+        .zip(out.iter_mut())
+        .for_each(|((a, b), o)| *o = f(a, b));
+}
+
+/// Pairwise `delta_e_76` of two equal length CIE LAB slices, written into `out`.
+pub fn delta_e_76_slice<T: DType>(a: &[[T; 3]], b: &[[T; 3]], out: &mut [T]) {
+    delta_e_slice(a, b, out, delta_e_76)
+}
+
+/// Pairwise `delta_e_ok` of two equal length OKLAB slices, written into `out`.
+pub fn delta_e_ok_slice<T: DType>(a: &[[T; 3]], b: &[[T; 3]], out: &mut [T]) {
+    delta_e_slice(a, b, out, delta_e_ok)
+}
+
+/// Pairwise `delta_e_2000` of two equal length CIE LAB slices, written into `out`.
+pub fn delta_e_2000_slice<T: DType>(a: &[[T; 3]], b: &[[T; 3]], out: &mut [T]) {
+    delta_e_slice(a, b, out, delta_e_2000)
+}
+
+// ### Delta E ### }}}
+
+// ### Contrast ### {{{
+
+/// Relative luminance of an sRGB color as defined by WCAG 2.
+///
+/// <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+pub fn relative_luminance<T: DType, const N: usize>(pixel: &[T; N]) -> T
+where
+    Channels<N>: ValidChannels,
+{
+    srgb_eotf(pixel[0]).fma(
+        0.2126.to_dt(),
+        srgb_eotf(pixel[1]).fma(0.7152.to_dt(), srgb_eotf(pixel[2]) * 0.0722.to_dt()),
+    )
+}
+
+/// Contrast ratio between two sRGB colors as defined by WCAG 2, ranging from 1 to 21 and independent of order.
+///
+/// <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+pub fn contrast_ratio<T: DType, const N: usize>(a: &[T; N], b: &[T; N]) -> T
+where
+    Channels<N>: ValidChannels,
+{
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05.to_dt()) / (a.min(b) + 0.05.to_dt())
+}
+
+/// Lightens or darkens the sRGB color `fg` by its OKLCH lightness until its `contrast_ratio` against `bg` reaches `target_ratio`.
+///
+/// Moves in the direction `fg` already sits relative to `bg` when possible, otherwise the opposite direction.
+/// If the target cannot be reached either way, `fg` ends at whichever of black or white has more contrast.
+/// Colors already meeting the target are unchanged. Chroma is reduced with `gamut_map_srgb` where necessary.
+pub fn adjust_to_contrast<T: DType>(fg: &mut [T; 3], bg: &[T; 3], target_ratio: T) {
+    if contrast_ratio(fg, bg) >= target_ratio {
+        return;
+    }
+    let mut lch = *fg;
+    convert_space::<T, 3>(Space::SRGB, Space::OKLCH, &mut lch);
+    let at = |l: T| {
+        let mut pixel = [l, lch[1], lch[2]];
+        convert_space::<T, 3>(Space::OKLCH, Space::SRGB, &mut pixel);
+        gamut_map_srgb(&mut pixel);
+        pixel
+    };
+
+    let (white, black) = ([T::ff32(1.0); 3], [T::ff32(0.0); 3]);
+    let (white_ok, black_ok) = (
+        contrast_ratio(&white, bg) >= target_ratio,
+        contrast_ratio(&black, bg) >= target_ratio,
+    );
+    let lighter = relative_luminance(fg) >= relative_luminance(bg);
+    let end = if white_ok && (lighter || !black_ok) {
+        T::ff32(1.0)
+    } else if black_ok {
+        T::ff32(0.0)
+    } else {
+        *fg = if contrast_ratio(&white, bg) >= contrast_ratio(&black, bg) {
+            white
+        } else {
+            black
+        };
+        return;
+    };
+
+    // lightness where the target fails and where it passes
+    let (mut fail, mut pass) = (lch[0], end);
+    for _ in 0..24 {
+        let mid = (fail + pass) / 2.0.to_dt();
+        if contrast_ratio(&at(mid), bg) >= target_ratio {
+            pass = mid
+        } else {
+            fail = mid
+        }
+    }
+    *fg = at(pass);
+}
+
+// ### Contrast ### }}}
+
+// ### Gamut ### {{{
+
+/// Just noticeable difference in OKLAB for `gamut_map_srgb`
+const GAMUT_JND: f32 = 0.02;
+/// Chroma precision for `gamut_map_srgb`
+const GAMUT_EPSILON: f32 = 0.0001;
+
+/// Maps an out of gamut sRGB color into 0.0..=1.0 by reducing its OKLCH chroma until clipping is imperceptible.
+/// Lightness and hue are preserved as closely as possible. Colors already in gamut are unchanged.
+/// Colors with NaN or infinite channels are only clipped, with NaN going to 0.0.
+///
+/// <https://www.w3.org/TR/css-color-4/#binsearch>
+pub fn gamut_map_srgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let in_gamut = |p: &[T; 3]| p.iter().all(|c| *c >= 0.0.to_dt() && *c <= 1.0.to_dt());
+    let clip = |p: [T; 3]| p.map(|c| c.max(0.0.to_dt()).min(1.0.to_dt()));
+    let delta_e = |lch: [T; 3], srgb: [T; 3]| {
+        let (mut a, mut b) = (lch, srgb);
+        lch_to_lab::<T, 3>(&mut a);
+        convert_space::<T, 3>(Space::SRGB, Space::OKLAB, &mut b);
+        delta_e_ok(&a, &b)
+    };
+
+    let rgb = [pixel[0], pixel[1], pixel[2]];
+    if in_gamut(&rgb) {
+        return;
+    } else if rgb.iter().any(|c| !c.is_finite()) {
+        [pixel[0], pixel[1], pixel[2]] = clip(rgb);
+        return;
+    }
+    let mut current = rgb;
+    convert_space::<T, 3>(Space::SRGB, Space::OKLCH, &mut current);
+
+    [pixel[0], pixel[1], pixel[2]] = if current[0] >= 1.0.to_dt() {
+        [1.0.to_dt(); 3]
+    } else if current[0] <= 0.0.to_dt() {
+        [0.0.to_dt(); 3]
+    } else if delta_e(current, clip(rgb)) < GAMUT_JND.to_dt() {
+        clip(rgb)
+    } else {
+        let (mut min, mut max, mut min_in_gamut) = (T::ff32(0.0), current[1], true);
+        while max - min > GAMUT_EPSILON.to_dt() {
+            current[1] = (min + max) / 2.0.to_dt();
+            let mut srgb = current;
+            convert_space::<T, 3>(Space::OKLCH, Space::SRGB, &mut srgb);
+            if min_in_gamut && in_gamut(&srgb) {
+                min = current[1];
+                continue;
+            }
+            let e = delta_e(current, clip(srgb));
+            if e < GAMUT_JND.to_dt() {
+                if T::ff32(GAMUT_JND) - e < GAMUT_EPSILON.to_dt() {
+                    break;
+                }
+                min_in_gamut = false;
+                min = current[1];
+            } else {
+                max = current[1];
+            }
+        }
+        convert_space::<T, 3>(Space::OKLCH, Space::SRGB, &mut current);
+        clip(current)
+    };
+}
+
+/// Highest OKLCH lightness at chroma `c` and hue `h` which is still within the sRGB gamut.
+///
+/// Scans down from white then bisects the boundary, so it doesn't need the Okhsl cusp approximation.
+/// Near the cusp the in gamut lightnesses can fall between scan steps, so then the lightness
+/// closest to the gamut is searched for before bisecting.
+/// Returns NaN if no lightness reaches `c` at that hue, as `c` is beyond the gamut's cusp.
+pub fn max_lightness_srgb<T: DType>(c: T, h: T) -> T {
+    let slack = T::ff32(1e-6);
+    // how far the furthest channel lands outside of 0.0..=1.0
+    let excess = |l: T| {
+        let mut pixel = [l, c, h];
+        convert_space::<T, 3>(Space::OKLCH, Space::SRGB, &mut pixel);
+        pixel.iter().fold(T::ff32(0.0), |e, c| e.max(-*c).max(*c - 1.0.to_dt()))
+    };
+    let in_gamut = |l: T| excess(l) <= slack;
+    const STEPS: usize = 64;
+    let at = |n: usize| T::ff32(n as f32 / STEPS as f32);
+    // lightness where the color is in gamut and where it is not
+    let (mut pass, mut fail) = match (0..=STEPS).rev().find(|n| in_gamut(at(*n))) {
+        Some(STEPS) => return T::ff32(1.0),
+        Some(step) => (at(step), at(step + 1)),
+        None => {
+            let best = (0..=STEPS)
+                .map(|n| (n, excess(at(n))))
+                .fold((0, T::ff32(f32::INFINITY)), |a, b| if b.1 < a.1 { b } else { a })
+                .0;
+            // ternary search the neighboring steps, as the excess falls then rises through the cusp
+            let (mut lo, mut hi) = (at(best.saturating_sub(1)), at((best + 1).min(STEPS)));
+            for _ in 0..48 {
+                let third = (hi - lo) / 3.0.to_dt();
+                if excess(lo + third) < excess(hi - third) {
+                    hi = hi - third
+                } else {
+                    lo = lo + third
+                }
+            }
+            let mid = (lo + hi) / 2.0.to_dt();
+            if !in_gamut(mid) {
+                return T::ff32(f32::NAN);
+            }
+            (mid, at((best + 1).min(STEPS)))
+        }
+    };
+    for _ in 0..24 {
+        let mid = (pass + fail) / 2.0.to_dt();
+        if in_gamut(mid) {
+            pass = mid
+        } else {
+            fail = mid
+        }
+    }
+    pass
+}
+
+/// OKLCH hue drift in degrees `clip_preserve_hue` tolerates before correcting
+const CLIP_HUE_THRESHOLD: f32 = 1.0;
+
+/// Clips an sRGB color into 0.0..=1.0, then rotates the clipped result back towards the original OKLCH hue
+/// if clipping drifted it by more than a degree. Results which clip to nearly gray are left as is.
+///
+/// Much cheaper than `gamut_map_srgb` which searches for the chroma that keeps the color closest overall,
+/// but only hue is protected here so lightness and chroma may shift noticeably.
+pub fn clip_preserve_hue<T: DType>(srgb: &mut [T; 3]) {
+    let clip = |p: &mut [T; 3]| p.iter_mut().for_each(|c| *c = c.max(0.0.to_dt()).min(1.0.to_dt()));
+    let mut target = *srgb;
+    convert_space::<T, 3>(Space::SRGB, Space::OKLCH, &mut target);
+    clip(srgb);
+
+    for _ in 0..4 {
+        let mut lch = *srgb;
+        convert_space::<T, 3>(Space::SRGB, Space::OKLCH, &mut lch);
+        let drift = (lch[2] - target[2] + 180.0.to_dt()).rem_euclid(360.0.to_dt()) - 180.0.to_dt();
+        if drift.abs() <= CLIP_HUE_THRESHOLD.to_dt() || lch[1] < GAMUT_JND.to_dt() {
+            break;
+        }
+        lch[2] = target[2];
+        convert_space::<T, 3>(Space::OKLCH, Space::SRGB, &mut lch);
+        clip(&mut lch);
+        *srgb = lch;
+    }
+}
+
+/// Clamps the first three channels of `pixel` in `space` to the extremes of that space's `srgb_quants`.
+///
+/// Cheap sanitization of user manipulated values before conversion. The result is not guaranteed
+/// to be within the sRGB gamut, only within the range each channel spans for sRGB colors.
+/// Hue channels and alpha are left untouched.
+pub fn clamp_to_gamut_ranges<T: DType, const N: usize>(pixel: &mut [T; N], space: Space)
+where
+    Channels<N>: ValidChannels,
+{
+    let quants = space.srgb_quants();
+    pixel.iter_mut().take(3).enumerate().for_each(|(n, c)| {
+        let (lo, hi) = (quants[0][n], quants[100][n]);
+        if lo.is_finite() && hi.is_finite() {
+            *c = c.max(lo.to_dt()).min(hi.to_dt())
+        }
+    });
+}
+
+/// Convert `pixel` from any `space` to display ready 8 bit sRGB.
+///
+/// Out of gamut colors go through `gamut_map_srgb` if `gamut_map` is set, otherwise they're clipped per channel.
+/// A NaN alpha such as from `str2col` without one is written as opaque,
+/// and NaN color channels such as CSS `none` are read as 0.0 like a browser would render them.
+pub fn to_display_rgb8<T: DType, const N: usize>(mut pixel: [T; N], from: Space, gamut_map: bool) -> [u8; N]
+where
+    Channels<N>: ValidChannels,
+{
+    pixel
+        .iter_mut()
+        .take(3)
+        .filter(|c| c.is_nan())
+        .for_each(|c| *c = 0.0.to_dt());
+    convert_space(from, Space::SRGB, &mut pixel);
+    if gamut_map {
+        gamut_map_srgb(&mut pixel)
+    }
+    if N == 4 && pixel[3].is_nan() {
+        pixel[3] = 1.0.to_dt()
+    }
+    srgb_to_irgb(pixel.map(|c| c.if64() as f32))
+}
+
+/// Simulates an sRGB color reproduced in a smaller RGB gamut, for soft proofing prints or narrow displays.
+///
+/// `lrgb_to_target` is the row-major matrix from linear sRGB into the target's linear RGB,
+/// which must share sRGB's white so 1.0 stays white. Like a relative colorimetric intent,
+/// colors the target can show are unchanged while the rest lose CIELCH chroma at the same lightness and hue until they fit.
+pub fn soft_proof<T: DType>(srgb: &mut [T; 3], lrgb_to_target: [[f32; 3]; 3]) {
+    let matrix = t(lrgb_to_target);
+    let slack = T::ff32(1e-6);
+    let fits = |lch: [T; 3]| {
+        let mut pixel = lch;
+        convert_space::<T, 3>(Space::CIELCH, Space::LRGB, &mut pixel);
+        mm(matrix, pixel)
+            .iter()
+            .all(|c| *c >= -slack && *c <= T::ff32(1.0) + slack)
+    };
+    let mut lch = *srgb;
+    convert_space::<T, 3>(Space::SRGB, Space::CIELCH, &mut lch);
+    if fits(lch) {
+        return;
+    }
+    // chroma where the color fits and where it does not
+    let (mut pass, mut fail) = (T::ff32(0.0), lch[1]);
+    for _ in 0..24 {
+        let mid = (pass + fail) / 2.0.to_dt();
+        if fits([lch[0], mid, lch[2]]) {
+            pass = mid
+        } else {
+            fail = mid
+        }
+    }
+    lch[1] = pass;
+    convert_space::<T, 3>(Space::CIELCH, Space::SRGB, &mut lch);
+    *srgb = lch;
+}
+
+// ### Gamut ### }}}
+
+// ### Adjustments ### {{{
+
+/// OKLCH hue window in degrees treated as skin tones by `saturate_protect_skin`
+pub const SKIN_HUES: [f32; 2] = [20.0, 50.0];
+
+/// Scales the OKLCH chroma of an sRGB color by `1.0 + amount`, except hues within `SKIN_HUES`
+/// where the change eases out towards nothing at the window's center so faces don't turn orange.
+///
+/// Negative `amount` desaturates with the same protection. Result is gamut mapped.
+pub fn saturate_protect_skin<T: DType>(srgb: &mut [T; 3], amount: T) {
+    let mut lch = *srgb;
+    convert_space::<T, 3>(Space::SRGB, Space::OKLCH, &mut lch);
+
+    let center = T::ff32((SKIN_HUES[0] + SKIN_HUES[1]) / 2.0);
+    let half = T::ff32((SKIN_HUES[1] - SKIN_HUES[0]) / 2.0);
+    let distance = (lch[2] - center + 180.0.to_dt()).rem_euclid(360.0.to_dt()) - 180.0.to_dt();
+    let weight = if distance.abs() < half {
+        // raised cosine so the boost is continuous at the window edges
+        (T::ff32(1.0) - (distance / half * core::f32::consts::PI.to_dt()).cos()) / 2.0.to_dt()
+    } else {
+        T::ff32(1.0)
+    };
+
+    lch[1] = (lch[1] * (T::ff32(1.0) + amount * weight)).max(0.0.to_dt());
+    convert_space::<T, 3>(Space::OKLCH, Space::SRGB, &mut lch);
+    gamut_map_srgb(&mut lch);
+    *srgb = lch;
+}
+
+/// Brightens or darkens an sRGB color by `stops` of exposure, doubling linear light per stop.
+///
+/// Values above 1.0 are kept for HDR workflows unless `clamp` is set.
+pub fn adjust_exposure<T: DType>(srgb: &mut [T; 3], stops: T, clamp: bool) {
+    let gain = T::ff32(2.0).powf(stops);
+    srgb_to_lrgb::<T, 3>(srgb);
+    srgb.iter_mut().for_each(|c| *c = *c * gain);
+    lrgb_to_srgb::<T, 3>(srgb);
+    if clamp {
+        srgb.iter_mut().for_each(|c| *c = c.max(0.0.to_dt()).min(1.0.to_dt()));
+    }
+}
+
+/// Lightness of an sRGB color on a 0 to 10 "value" scale for traditional painting workflows, as CIE L* / 10.
+///
+/// Close to but not exactly Munsell value, which is defined by its own renotation tables.
+pub fn lab_value<T: DType>(srgb: &[T; 3]) -> T {
+    let mut lab = *srgb;
+    convert_space::<T, 3>(Space::SRGB, Space::CIELAB, &mut lab);
+    lab[0] / 10.0.to_dt()
+}
+
+/// Sets the `lab_value` of an sRGB color while keeping its CIE a* and b*.
+///
+/// The result is not gamut mapped, so saturated colors pushed far lighter or darker may leave 0.0..=1.0.
+pub fn set_lab_value<T: DType>(srgb: &mut [T; 3], value: T) {
+    convert_space::<T, 3>(Space::SRGB, Space::CIELAB, srgb);
+    srgb[0] = value * 10.0.to_dt();
+    convert_space::<T, 3>(Space::CIELAB, Space::SRGB, srgb);
+}
+
+/// Posterize an sRGB color to `levels` evenly spaced steps of lightness and chroma in `space`, then gamut map back.
+///
+/// LAB spaces are banded through their LCH form so grays stay neutral, and hues are left smooth.
+/// RGB and XYZ spaces band every channel. In OKLAB or OKLCH the bands are perceptually even,
+/// unlike posterizing sRGB directly. Channels span the same range as `generate_lut` samples. `levels` is at least 2.
+pub fn posterize<T: DType>(srgb: &mut [T; 3], levels: usize, space: Space) {
+    let space = match space {
+        Space::CIELAB => Space::CIELCH,
+        Space::OKLAB => Space::OKLCH,
+        Space::JZAZBZ => Space::JZCZHZ,
+        s => s,
+    };
+    let steps = T::ff32((levels.max(2) - 1) as f32);
+    let [min, max] = channel_domain(space);
+    let hue = hue_channel(space).map(|(h, _)| h);
+    convert_space::<T, 3>(Space::SRGB, space, srgb);
+    srgb.iter_mut()
+        .enumerate()
+        .filter(|(n, _)| Some(*n) != hue)
+        .for_each(|(n, c)| {
+            let (lo, range) = (T::ff32(min[n]), T::ff32(max[n] - min[n]));
+            let pos = ((*c - lo) / range).max(0.0.to_dt()).min(1.0.to_dt());
+            *c = lo + (pos * steps + 0.5.to_dt()).trunc() / steps * range
+        });
+    // the bottom lightness band is black, which can't carry chroma
+    if hue == Some(2) && srgb[0] <= min[0].to_dt() {
+        srgb[1] = 0.0.to_dt()
+    }
+    convert_space::<T, 3>(space, Space::SRGB, srgb);
+    gamut_map_srgb(srgb);
+}
+
+/// Duotone an sRGB color, replacing it with a ramp from `dark` to `light` placed by its OKLAB lightness.
+///
+/// Black lands on `dark` and white on `light`, interpolated in OKLAB so the ramp is perceptually even.
+/// The ramp is not gamut mapped, so distant hues may dip slightly outside of 0.0..=1.0 midway.
+pub fn duotone<T: DType>(srgb: &mut [T; 3], dark: &[T; 3], light: &[T; 3]) {
+    let [mut dark, mut light] = [*dark, *light];
+    for pixel in [&mut *srgb, &mut dark, &mut light] {
+        convert_space::<T, 3>(Space::SRGB, Space::OKLAB, pixel);
+    }
+    let t = srgb[0].clamp(0.0.to_dt(), 1.0.to_dt());
+    *srgb = mix(&dark, &light, t, Space::OKLAB);
+    convert_space::<T, 3>(Space::OKLAB, Space::SRGB, srgb);
+}
+
+/// Flips the lightness of an sRGB color for deriving dark themes from light ones, keeping its OKLCH hue and chroma.
+///
+/// Unlike inverting RGB, which also turns blue into yellow, a dark blue becomes a light blue.
+/// Lightness is flipped on the toe corrected Lr scale so dark shades map to evenly spaced light ones.
+/// Chroma which doesn't fit at the new lightness is gamut mapped away.
+pub fn invert_lightness<T: DType>(srgb: &mut [T; 3]) {
+    convert_space::<T, 3>(Space::SRGB, Space::OKLCH, srgb);
+    oklab_to_oklrab(srgb);
+    srgb[0] = T::ff32(1.0) - srgb[0];
+    oklrab_to_oklab(srgb);
+    convert_space::<T, 3>(Space::OKLCH, Space::SRGB, srgb);
+    gamut_map_srgb(srgb);
+}
+
+/// Gray world white balance of sRGB `pixels`, shifting every pixel by the negated mean CIE a* and b*
+/// so the image averages out to neutral.
+///
+/// Lightness is untouched. The result is not gamut mapped, so strong corrections may leave 0.0..=1.0.
+/// Images dominated by one genuinely colored subject will be pushed towards its complement.
+pub fn auto_white_balance<T: DType>(pixels: &mut [[T; 3]]) {
+    if pixels.is_empty() {
+        return;
+    }
+    convert_space_chunked(Space::SRGB, Space::CIELAB, pixels);
+    let count = T::ff64(pixels.len() as f64);
+    let [a, b] = pixels
+        .iter()
+        .fold([T::ff32(0.0); 2], |[a, b], p| [a + p[1], b + p[2]])
+        .map(|c| c / count);
+    pixels.iter_mut().for_each(|p| [p[1], p[2]] = [p[1] - a, p[2] - b]);
+    convert_space_chunked(Space::CIELAB, Space::SRGB, pixels);
+}
+
+// ### Adjustments ### }}}
+
+// ### Harmony ### {{{
+
+/// Color harmony schemes for `harmony`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HarmonyScheme {
+    /// Opposite hue
+    Complementary,
+    /// Three hues evenly spaced
+    Triadic,
+    /// Neighboring hues 30 degrees to either side
+    Analogous,
+    /// Four hues evenly spaced
+    Tetradic,
+    /// The two hues neighboring the complement
+    SplitComplementary,
+}
+
+impl HarmonyScheme {
+    /// Hue rotations in degrees applied to the base color
+    pub const fn rotations(&self) -> &'static [f32] {
+        match self {
+            HarmonyScheme::Complementary => &[180.0],
+            HarmonyScheme::Triadic => &[120.0, 240.0],
+            HarmonyScheme::Analogous => &[-30.0, 30.0],
+            HarmonyScheme::Tetradic => &[90.0, 180.0, 270.0],
+            HarmonyScheme::SplitComplementary => &[150.0, 210.0],
+        }
+    }
+}
+
+/// Generate a color harmony from `base_srgb`, returning the base followed by one color per rotation of `scheme`.
+///
+/// Hues are rotated in OKLCH keeping lightness and chroma, then brought back into sRGB with `gamut_map_srgb`.
+pub fn harmony<T: DType>(base_srgb: &[T; 3], scheme: HarmonyScheme) -> Vec<[T; 3]> {
+    let mut base = *base_srgb;
+    convert_space(Space::SRGB, Space::OKLCH, &mut base);
+
+    core::iter::once(*base_srgb)
+        .chain(scheme.rotations().iter().map(|r| {
+            let mut pixel = [base[0], base[1], (base[2] + r.to_dt()).rem_euclid(360.0.to_dt())];
+            convert_space(Space::OKLCH, Space::SRGB, &mut pixel);
+            gamut_map_srgb(&mut pixel);
+            pixel
+        }))
+        .collect()
+}
+
+fn ramp<T: DType>(base_srgb: &[T; 3], count: usize, step: impl Fn([T; 3], T) -> [T; 3]) -> Vec<[T; 3]> {
+    let mut base = *base_srgb;
+    convert_space(Space::SRGB, Space::OKLCH, &mut base);
+    (0..count)
+        .map(|n| {
+            let mut pixel = step(base, T::ff32(n as f32 / count as f32));
+            convert_space(Space::OKLCH, Space::SRGB, &mut pixel);
+            gamut_map_srgb(&mut pixel);
+            pixel
+        })
+        .collect()
+}
+
+/// `count` colors starting at `base_srgb` and stepping its OKLCH lightness evenly toward white, dark to light.
+///
+/// Hue and chroma are kept where possible, with `gamut_map_srgb` reducing chroma near white.
+/// Pairs with `shades` to build a full 50 to 900 style scale from a single seed.
+pub fn tints<T: DType>(base_srgb: &[T; 3], count: usize) -> Vec<[T; 3]> {
+    ramp(base_srgb, count, |lch, t| [lch[0].lerp(1.0.to_dt(), t), lch[1], lch[2]])
+}
+
+/// `count` colors starting at `base_srgb` and stepping its OKLCH lightness evenly toward black, light to dark.
+pub fn shades<T: DType>(base_srgb: &[T; 3], count: usize) -> Vec<[T; 3]> {
+    ramp(base_srgb, count, |lch, t| [lch[0].lerp(0.0.to_dt(), t), lch[1], lch[2]])
+}
+
+/// `count` colors starting at `base_srgb` and stepping its OKLCH chroma evenly toward gray at the same lightness.
+pub fn tones<T: DType>(base_srgb: &[T; 3], count: usize) -> Vec<[T; 3]> {
+    ramp(base_srgb, count, |lch, t| [lch[0], lch[1].lerp(0.0.to_dt(), t), lch[2]])
+}
+
+// ### Harmony ### }}}
+
+// ### Palette ### {{{
+
+/// A collection of colors in a single `Space` with palette level operations.
+///
+/// Perceptual operations are performed in OKLAB regardless of `space`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Palette<T: DType> {
+    /// Colors of the palette, in `space`
+    pub colors: Vec<[T; 3]>,
+    /// Space `colors` are stored in
+    pub space: Space,
+}
+
+impl<T: DType> Palette<T> {
+    /// Create a palette from `colors` which are in `space`.
+    pub fn new(colors: Vec<[T; 3]>, space: Space) -> Self {
+        Self { colors, space }
+    }
+
+    fn convert(&self, pixel: &[T; 3], to: Space) -> [T; 3] {
+        let mut pixel = *pixel;
+        convert_space::<T, 3>(self.space, to, &mut pixel);
+        pixel
+    }
+
+    /// Index of the color perceptually closest to `srgb` by `delta_e_ok`, or None if the palette is empty.
+    pub fn nearest(&self, srgb: &[T; 3]) -> Option<usize> {
+        let mut target = *srgb;
+        convert_space::<T, 3>(Space::SRGB, Space::OKLAB, &mut target);
+        self.colors
+            .iter()
+            .map(|c| delta_e_ok(&self.convert(c, Space::OKLAB), &target))
+            .enumerate()
+            .fold(None, |acc: Option<(usize, T)>, (n, e)| match acc {
+                Some((_, best)) if best <= e => acc,
+                _ => Some((n, e)),
+            })
+            .map(|(n, _)| n)
+    }
+
+    /// Sort colors from darkest to lightest by OKLAB lightness.
+    pub fn sort_by_lightness(&mut self) {
+        let mut keyed: Vec<(T, [T; 3])> = self
+            .colors
+            .iter()
+            .map(|c| (self.convert(c, Space::OKLAB)[0], *c))
+            .collect();
+        keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+        self.colors = keyed.into_iter().map(|(_, c)| c).collect();
+    }
+
+    /// Remove colors within `threshold` `delta_e_ok` of an earlier color, keeping the first of each group.
+    pub fn dedup_perceptual(&mut self, threshold: T) {
+        let mut kept: Vec<[T; 3]> = Vec::with_capacity(self.colors.len());
+        self.colors = self
+            .colors
+            .iter()
+            .filter(|c| {
+                let lab = self.convert(c, Space::OKLAB);
+                let unique = kept.iter().all(|k| delta_e_ok(k, &lab) > threshold);
+                if unique {
+                    kept.push(lab)
+                }
+                unique
+            })
+            .copied()
+            .collect();
+    }
+
+    /// Append the `harmony` of every current color, excluding the bases themselves.
+    pub fn extend_harmony(&mut self, scheme: HarmonyScheme) {
+        let extra: Vec<[T; 3]> = self
+            .colors
+            .iter()
+            .flat_map(|c| harmony(&self.convert(c, Space::SRGB), scheme).into_iter().skip(1))
+            .map(|mut c| {
+                convert_space::<T, 3>(Space::SRGB, self.space, &mut c);
+                c
+            })
+            .collect();
+        self.colors.extend(extra);
+    }
+}
+
+// ### Palette ### }}}
+
+// ### Histogram ### {{{
+
+/// Histogram of the hues in `srgb` with `bins` equal bins starting from 0 degrees.
+///
+/// Each pixel is converted to the polar `space` and weighted by its chroma,
+/// or saturation times value for HSV, so near-gray pixels barely contribute.
+/// Weights are normalized to sum to 1 unless every pixel is gray.
+///
+/// Returns `None` if `space` has no hue channel. Zero `bins` gives an empty histogram.
+pub fn hue_histogram<T: DType>(srgb: &[[T; 3]], bins: usize, space: Space) -> Option<Vec<T>> {
+    hue_channel(space)?;
+    if bins == 0 {
+        return Some(Vec::new());
+    }
+    let edges: Vec<T> = (1..bins).map(|n| T::ff32(n as f32 * 360.0 / bins as f32)).collect();
+    let mut histogram = vec![T::ff32(0.0); bins];
+
+    srgb.iter().for_each(|pixel| {
+        let mut pixel = *pixel;
+        convert_space(Space::SRGB, space, &mut pixel);
+        let (hue, weight) = match space {
+            Space::HSV => (pixel[0] * 360.0.to_dt(), pixel[1] * pixel[2]),
+            _ => (pixel[2], pixel[1]),
+        };
+        let bin = edges.partition_point(|e| *e <= hue.rem_euclid(360.0.to_dt()));
+        histogram[bin] = histogram[bin] + weight;
+    });
+
+    let sum = histogram.iter().fold(T::ff32(0.0), |acc, w| acc + *w);
+    if sum > 0.0.to_dt() {
+        histogram.iter_mut().for_each(|w| *w = *w / sum);
+    }
+    Some(histogram)
+}
+
+/// Colorfulness of an sRGB image as its mean OKLCH chroma.
+///
+/// Grayscale images score 0 while fully saturated images reach around 0.2 to 0.3, useful for ranking.
+/// An empty slice scores 0.
+pub fn colorfulness<T: DType>(srgb: &[[T; 3]]) -> T {
+    if srgb.is_empty() {
+        return 0.0.to_dt();
+    }
+    let sum = srgb.iter().fold(T::ff32(0.0), |acc, pixel| {
+        let mut pixel = *pixel;
+        convert_space(Space::SRGB, Space::OKLAB, &mut pixel);
+        acc + pixel[1].fma(pixel[1], pixel[2] * pixel[2]).sqrt()
+    });
+    sum / T::ff32(srgb.len() as f32)
+}
+
+// ### Histogram ### }}}
+
+// ### Averaging ### {{{
+
+/// Box filter average of sRGB `pixels` performed in linear light, as averaging the encoded values is too dark.
+///
+/// With 4 channels color is weighted by alpha, equivalent to averaging premultiplied pixels
+/// then un-premultiplying the result. An empty slice averages to all zeros.
+pub fn average_linear<T: DType, const N: usize>(pixels: &[[T; N]]) -> [T; N]
+where
+    Channels<N>: ValidChannels,
+{
+    let mut sum = [T::ff32(0.0); N];
+    pixels.iter().for_each(|pixel| {
+        let weight = if N == 4 { pixel[3] } else { 1.0.to_dt() };
+        sum.iter_mut()
+            .zip(pixel.iter())
+            .take(3)
+            .for_each(|(s, c)| *s = srgb_eotf(*c).fma(weight, *s));
+        if N == 4 {
+            sum[3] = sum[3] + weight
+        }
+    });
+
+    let weight = if N == 4 { sum[3] } else { T::ff32(pixels.len() as f32) };
+    if weight > 0.0.to_dt() {
+        sum.iter_mut().take(3).for_each(|c| *c = srgb_oetf(*c / weight));
+    }
+    if N == 4 && !pixels.is_empty() {
+        sum[3] = sum[3] / T::ff32(pixels.len() as f32)
+    }
+    sum
+}
+
+// ### Averaging ### }}}
+
+// ### Interpolation ### {{{
+
+/// Index and period of the hue channel for spaces which have one
+fn hue_channel(space: Space) -> Option<(usize, f32)> {
+    match space {
+        Space::HSV => Some((0, 1.0)),
+        Space::CIELCH | Space::OKLCH | Space::JZCZHZ => Some((2, 360.0)),
+        Space::SRGB | Space::LRGB | Space::XYZ | Space::CIELAB | Space::OKLAB | Space::JZAZBZ => None,
+    }
+}
+
+/// Whether moving a hue in degrees from `prev` to `new` the short way around crosses 0/360.
+///
+/// Hues are wrapped into `0.0..360.0` first. Exactly opposite hues take the increasing direction,
+/// matching `HueInterpolation::Shorter`.
+pub fn wrapped_hue<T: DType>(prev: T, new: T) -> bool {
+    let (prev, new) = unwrap_hues(prev, new, 360.0.to_dt(), HueInterpolation::Shorter);
+    prev.max(new) >= 360.0.to_dt()
+}
+
+/// CSS Color 4 hue interpolation methods, deciding which way around the circle two hues are joined.
+///
+/// <https://www.w3.org/TR/css-color-4/#hue-interpolation>
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HueInterpolation {
+    /// Take the arc of 180 degrees or less. Exactly opposite hues move up like `mix` always has,
+    /// where CSS would keep moving in whichever direction the hues are listed
+    #[default]
+    Shorter,
+    /// Take the arc of 180 degrees or more
+    Longer,
+    /// Always move up through the hues, wrapping past 360
+    Increasing,
+    /// Always move down through the hues, wrapping past 0
+    Decreasing,
+}
+
+/// Hue in degrees `t` of the way from `h1` to `h2` following `method`, wrapped into `0.0..360.0`.
+///
+/// Both hues are first wrapped into range, then one is moved a turn as needed
+/// so the plain linear interpolation between them travels the right way.
+///
+/// <https://www.w3.org/TR/css-color-4/#hue-interpolation>
+pub fn interpolate_hue<T: DType>(h1: T, h2: T, t: T, method: HueInterpolation) -> T {
+    lerp_hue(h1, h2, t, 360.0.to_dt(), method)
+}
+
+/// `interpolate_hue` for any `period`, such as HSV's 1.0
+fn lerp_hue<T: DType>(h1: T, h2: T, t: T, period: T, method: HueInterpolation) -> T {
+    let (h1, h2) = unwrap_hues(h1, h2, period, method);
+    wrap_hue(h1.lerp(h2, t), period)
+}
+
+/// Wraps both hues into `0.0..period` then moves one up a turn so going straight
+/// from `h1` to `h2` travels the way `method` asks
+fn unwrap_hues<T: DType>(h1: T, h2: T, period: T, method: HueInterpolation) -> (T, T) {
+    let half = period / 2.0.to_dt();
+    let (mut h1, mut h2) = (h1.rem_euclid(period), h2.rem_euclid(period));
+    let delta = h2 - h1;
+    match method {
+        HueInterpolation::Shorter if delta > half => h1 = h1 + period,
+        HueInterpolation::Shorter if delta <= -half => h2 = h2 + period,
+        HueInterpolation::Longer if delta > 0.0.to_dt() && delta < half => h1 = h1 + period,
+        HueInterpolation::Longer if delta > -half && delta <= 0.0.to_dt() => h2 = h2 + period,
+        HueInterpolation::Increasing if delta < 0.0.to_dt() => h2 = h2 + period,
+        HueInterpolation::Decreasing if delta > 0.0.to_dt() => h1 = h1 + period,
+        _ => (),
+    }
+    (h1, h2)
+}
+
+/// CSS `color-mix()` of `a` and `b` which are both in `space`, weighted by percentages `pa` and `pb`.
+///
+/// Like CSS either percentage may be NaN for omitted, taking whatever the other leaves of 100,
+/// or 50 each if both are. Percentages summing over 100 are scaled down to it. Summing under 100 they're scaled up,
+/// and with 4 channels the result's alpha is multiplied by the sum as a fraction. Returns None on a sum of 0
+/// or a negative percentage.
+///
+/// Colors are premultiplied by alpha for the mix, hues join by `hue`, and missing NaN components
+/// take the other color's value like `mix`.
+///
+/// <https://www.w3.org/TR/css-color-5/#color-mix>
+pub fn color_mix<T: DType, const N: usize>(
+    space: Space,
+    a: [T; N],
+    pa: T,
+    b: [T; N],
+    pb: T,
+    hue: HueInterpolation,
+) -> Option<[T; N]>
+where
+    Channels<N>: ValidChannels,
+{
+    let hundred = T::ff32(100.0);
+    let (pa, pb) = match (pa.is_nan(), pb.is_nan()) {
+        (true, true) => (50.0.to_dt(), 50.0.to_dt()),
+        (true, false) => (hundred - pb, pb),
+        (false, true) => (pa, hundred - pa),
+        (false, false) => (pa, pb),
+    };
+    let sum = pa + pb;
+    if pa < 0.0.to_dt() || pb < 0.0.to_dt() || sum <= 0.0.to_dt() {
+        return None;
+    }
+    let t = pb / sum;
+    let hue_index = hue_channel(space);
+
+    // a missing alpha takes the other's before premultiplying so both are weighted alike
+    let (mut a, mut b) = (a, b);
+    if N == 4 {
+        if a[3].is_nan() {
+            a[3] = b[3]
+        } else if b[3].is_nan() {
+            b[3] = a[3]
+        }
+    }
+
+    // premultiply everything but the hue, skipping when alpha is missing from both
+    let premultiply = |mut pixel: [T; N]| {
+        if N == 4 && !pixel[3].is_nan() {
+            let alpha = pixel[3];
+            pixel.iter_mut().take(3).enumerate().for_each(|(n, c)| {
+                if Some(n) != hue_index.map(|h| h.0) {
+                    *c = *c * alpha
+                }
+            });
+        }
+        pixel
+    };
+    let (a, b) = (premultiply(a), premultiply(b));
+
+    let mut result = mix_hue(&a, &b, t, space, hue);
+
+    if N == 4 {
+        let alpha = result[3];
+        if alpha != 0.0.to_dt() && !alpha.is_nan() {
+            result.iter_mut().take(3).enumerate().for_each(|(n, c)| {
+                if Some(n) != hue_index.map(|h| h.0) {
+                    *c = *c / alpha
+                }
+            });
+        }
+        if sum < hundred {
+            result[3] = result[3] * sum / hundred
+        }
+    }
+    Some(result)
+}
+
+/// Interpolate between colors `a` and `b` which are both in `space`, with `t` of 0.0 being `a` and 1.0 being `b`.
+///
+/// Hues take the shorter arc. Following CSS Color 4, a NaN "missing" component takes the other color's value,
+/// and only stays NaN if it is missing from both. Alpha is interpolated linearly.
+///
+/// <https://www.w3.org/TR/css-color-4/#interpolation-missing>
+pub fn mix<T: DType, const N: usize>(a: &[T; N], b: &[T; N], t: T, space: Space) -> [T; N]
+where
+    Channels<N>: ValidChannels,
+{
+    mix_hue(a, b, t, space, HueInterpolation::Shorter)
+}
+
+/// Same as `mix` but hues are joined following `method`.
+pub fn mix_hue<T: DType, const N: usize>(a: &[T; N], b: &[T; N], t: T, space: Space, method: HueInterpolation) -> [T; N]
+where
+    Channels<N>: ValidChannels,
+{
+    let hue = hue_channel(space);
+    let mut result = *a;
+    result.iter_mut().zip(b.iter()).enumerate().for_each(|(n, (x, y))| {
+        let (x0, y0) = match (x.is_nan(), y.is_nan()) {
+            (true, true) => return,
+            (true, false) => (*y, *y),
+            (false, true) => (*x, *x),
+            (false, false) => (*x, *y),
+        };
+        *x = match hue {
+            Some((h, period)) if h == n => lerp_hue(x0, y0, t, period.to_dt(), method),
+            _ => x0.lerp(y0, t),
+        };
+    });
+    result
+}
+
+/// Smooth gradient through `stops` which are all in `space`, returning `samples` colors in `space`.
+///
+/// Each channel is a monotone cubic spline (Fritsch-Carlson) over evenly spaced stops, so it never overshoots
+/// between two stops the way Catmull-Rom can. Hues take the shorter arc between neighboring stops.
+/// Stops land exactly on samples whose parameter matches, such as every 4th sample for 3 stops and 9 samples.
+///
+/// <https://en.wikipedia.org/wiki/Monotone_cubic_interpolation>
+pub fn spline_gradient<T: DType>(stops: &[[T; 3]], samples: usize, space: Space) -> Vec<[T; 3]> {
+    let Some(first) = stops.first() else {
+        return Vec::new();
+    };
+    if stops.len() < 2 || samples < 2 {
+        return vec![*first; samples];
+    }
+    let hue = hue_channel(space);
+    let mut points = stops.to_vec();
+    if let Some((h, period)) = hue {
+        let mut previous = first[h];
+        points.iter_mut().skip(1).for_each(|p| {
+            let (from, to) = unwrap_hues(previous, p[h], period.to_dt(), HueInterpolation::Shorter);
+            p[h] = previous + (to - from);
+            previous = p[h];
+        });
+    }
+
+    let segments = points.len() - 1;
+    // Fritsch-Carlson tangents per stop per channel
+    let mut tangents = vec![[T::ff32(0.0); 3]; points.len()];
+    for c in 0..3 {
+        let secant = |i: usize| points[i + 1][c] - points[i][c];
+        tangents[0][c] = secant(0);
+        tangents[segments][c] = secant(segments - 1);
+        for (i, tangent) in tangents.iter_mut().enumerate().take(segments).skip(1) {
+            let (d0, d1) = (secant(i - 1), secant(i));
+            tangent[c] = if d0 * d1 <= 0.0.to_dt() {
+                0.0.to_dt()
+            } else {
+                (d0 + d1) / 2.0.to_dt()
+            };
+        }
+        for i in 0..segments {
+            let d = secant(i);
+            if d == 0.0.to_dt() {
+                tangents[i][c] = 0.0.to_dt();
+                tangents[i + 1][c] = 0.0.to_dt();
+                continue;
+            }
+            let (a, b) = (tangents[i][c] / d, tangents[i + 1][c] / d);
+            let r = a.fma(a, b * b);
+            if r > 9.0.to_dt() {
+                let tau = T::ff32(3.0) / r.sqrt();
+                tangents[i][c] = tau * a * d;
+                tangents[i + 1][c] = tau * b * d;
+            }
+        }
+    }
+
+    (0..samples)
+        .map(|j| {
+            let pos = T::ff32((j * segments) as f32 / (samples - 1) as f32);
+            let i = (j * segments / (samples - 1)).min(segments - 1);
+            let s = pos - T::ff32(i as f32);
+            let (s2, s3) = (s * s, s * s * s);
+            let h00 = T::ff32(2.0) * s3 - T::ff32(3.0) * s2 + 1.0.to_dt();
+            let h10 = s3 - T::ff32(2.0) * s2 + s;
+            let h01 = T::ff32(-2.0) * s3 + T::ff32(3.0) * s2;
+            let h11 = s3 - s2;
+            let mut pixel = [0, 1, 2]
+                .map(|c| h00 * points[i][c] + h10 * tangents[i][c] + h01 * points[i + 1][c] + h11 * tangents[i + 1][c]);
+            if let Some((h, period)) = hue {
+                pixel[h] = pixel[h].rem_euclid(period.to_dt())
+            }
+            pixel
+        })
+        .collect()
+}
+
+// ### Interpolation ### }}}
+
+// ### Random ### {{{
+
+/// Random color in `space` which is always within the sRGB gamut.
+///
+/// Channels are sampled uniformly between the space's `srgb_quants` extremes,
+/// with hue sampled over the full circle, and out of gamut samples are rejected.
+#[cfg(feature = "rand")]
+pub fn random_color<T: DType>(space: Space, rng: &mut impl rand::RngCore) -> [T; 3] {
+    use rand::Rng;
+    let quants = space.srgb_quants();
+    let (lower, upper) = (quants[0], quants[100]);
+    loop {
+        let mut pixel: [T; 3] = [0, 1, 2].map(|n| {
+            let (lower, upper) = match (lower[n], upper[n]) {
+                (l, u) if l.is_finite() && u.is_finite() => (l, u),
+                _ if space == Space::HSV => (0.0, 1.0),
+                _ => (0.0, 360.0),
+            };
+            T::ff32(rng.gen_range(lower..=upper))
+        });
+        let sample = pixel;
+        convert_space(space, Space::SRGB, &mut pixel);
+        if pixel.iter().all(|c| *c >= 0.0.to_dt() && *c <= 1.0.to_dt()) {
+            return sample;
+        }
+    }
+}
+
+/// Film grain for an sRGB color, offsetting only OKLAB L by triangular noise within `amount` either way.
+///
+/// Working in OKLAB keeps the grain perceptually even instead of crushed in shadows and blown in highlights,
+/// and leaves hue and chroma alone. The mean lightness over many samples is unchanged.
+/// Not clipped, so grain on colors near black or white may leave 0.0..=1.0.
+#[cfg(feature = "rand")]
+pub fn add_grain<T: DType>(srgb: &mut [T; 3], amount: T, rng: &mut impl rand::RngCore) {
+    use rand::Rng;
+    convert_space::<T, 3>(Space::SRGB, Space::OKLAB, srgb);
+    let noise: f32 = rng.gen::<f32>() - rng.gen::<f32>();
+    srgb[0] = T::ff32(noise).fma(amount, srgb[0]);
+    convert_space::<T, 3>(Space::OKLAB, Space::SRGB, srgb);
+}
+
+// ### Random ### }}}
+
+// ### Space ### {{{
+
+/// Defines colorspace pixels will take.
+///
+/// With the `serde` feature spaces serialize as their lowercase names, like `"oklch"`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Space {
     /// Gamma-corrected sRGB.
     SRGB,
@@ -599,6 +2587,31 @@ impl Display for Space {
 }
 
 impl Space {
+    /// Forgiving alternative to `Space::try_from` for user input.
+    ///
+    /// Ignores case, whitespace, `-`, `_` and `*` so `"cie-lab"`, `"ok_lch"` or `"L*a*b*"` all parse,
+    /// and accepts a few common synonyms like `"linear"` for LRGB or `"jab"` for JzAzBz.
+    pub fn from_str_fuzzy(value: &str) -> Option<Self> {
+        let squashed: String = value
+            .chars()
+            .filter(|c| !(c.is_whitespace() || ['-', '_', '*'].contains(c)))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match squashed.strip_prefix("cie").unwrap_or(&squashed) {
+            "srgb" | "gammargb" => Some(Space::SRGB),
+            "hsv" | "hsb" => Some(Space::HSV),
+            "lrgb" | "rgb" | "linear" | "linearrgb" | "linearsrgb" | "srgblinear" => Some(Space::LRGB),
+            "xyz" | "xyz65" | "xyzd65" => Some(Space::XYZ),
+            "lab" => Some(Space::CIELAB),
+            "lch" | "lchab" => Some(Space::CIELCH),
+            "oklab" => Some(Space::OKLAB),
+            "oklch" => Some(Space::OKLCH),
+            "jzazbz" | "jab" => Some(Space::JZAZBZ),
+            "jzczhz" | "jch" => Some(Space::JZCZHZ),
+            _ => None,
+        }
+    }
+
     /// Returns 3 channels letters for user-facing colorspace controls
     pub fn channels(&self) -> [char; 3] {
         match self {
@@ -615,6 +2628,55 @@ impl Space {
         }
     }
 
+    /// Wraps the hue channel of HSV and the cylindrical UCS spaces back into their half-open range,
+    /// `0.0..1.0` and `0.0..360.0` respectively.
+    ///
+    /// Useful after manually shifting a hue. Other spaces are left untouched.
+    pub fn normalize_hue<T: DType, const N: usize>(&self, pixel: &mut [T; N])
+    where
+        Channels<N>: ValidChannels,
+    {
+        match self {
+            Space::HSV => pixel[0] = wrap_hue(pixel[0], 1.0.to_dt()),
+            Space::CIELCH | Space::OKLCH | Space::JZCZHZ => pixel[2] = wrap_hue(pixel[2], 360.0.to_dt()),
+            Space::SRGB | Space::LRGB | Space::XYZ | Space::CIELAB | Space::OKLAB | Space::JZAZBZ => (),
+        }
+    }
+
+    /// Whether all channels are expected within 0.0..=1.0 for SDR content,
+    /// making clamping or gamut mapping to that range meaningful.
+    pub const fn is_bounded(&self) -> bool {
+        match self {
+            Space::SRGB | Space::HSV | Space::LRGB => true,
+            Space::XYZ
+            | Space::CIELAB
+            | Space::CIELCH
+            | Space::OKLAB
+            | Space::OKLCH
+            | Space::JZAZBZ
+            | Space::JZCZHZ => false,
+        }
+    }
+
+    /// Whether the space encodes scene light rather than being relative to a display.
+    /// Scene-referred values are unbounded and should not be clamped.
+    ///
+    /// Every space currently in colcon is display-referred.
+    pub const fn is_scene_referred(&self) -> bool {
+        match self {
+            Space::SRGB
+            | Space::HSV
+            | Space::LRGB
+            | Space::XYZ
+            | Space::CIELAB
+            | Space::CIELCH
+            | Space::OKLAB
+            | Space::OKLCH
+            | Space::JZAZBZ
+            | Space::JZCZHZ => false,
+        }
+    }
+
     /// All color spaces
     pub const ALL: &'static [Space] = &[
         Space::SRGB,
@@ -649,6 +2711,38 @@ impl Space {
     }
 }
 
+/// A color's channels paired with the `Space` they're in, for storing colors in configs.
+///
+/// Serializes as `{"space":"oklch","values":[0.6,0.1,120.0]}`.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(bound(
+    serialize = "[f32; N]: serde::Serialize",
+    deserialize = "[f32; N]: serde::Deserialize<'de>"
+))]
+pub struct ColorValue<const N: usize>
+where
+    Channels<N>: ValidChannels,
+{
+    /// Space of `values`
+    pub space: Space,
+    /// Channels of the color, with alpha last if `N` is 4
+    pub values: [f32; N],
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize> ColorValue<N>
+where
+    Channels<N>: ValidChannels,
+{
+    /// Same color with its values converted to `space`
+    pub fn to_space(self, space: Space) -> Self {
+        let mut values = self.values;
+        convert_space(self.space, space, &mut values);
+        Self { space, values }
+    }
+}
+
 // ### Space ### }}}
 
 // ### Convert Space ### {{{
@@ -665,6 +2759,20 @@ macro_rules! op_chunk {
     };
 }
 
+macro_rules! op_push {
+    ($func:ident, $data:expr) => {
+        $data.push($func)
+    };
+}
+
+macro_rules! op_traced {
+    ($func:ident, $data:expr) => {
+        $func(&mut *$data.0)
+    };
+}
+
+// Every route walks the tree without revisiting a space, so the sRGB transfer is applied at most once
+// and never undone within the same conversion. HSV hangs off sRGB, so LRGB -> HSV must encode gamma.
 #[rustfmt::skip]
 macro_rules! graph {
     ($recurse:ident, $data:expr, $from:expr, $to:expr, $op:ident) => {
@@ -682,10 +2790,10 @@ macro_rules! graph {
             (Space::JZCZHZ, Space::JZCZHZ) => (),
 
             //endcaps
-            (Space::SRGB, Space::HSV) => $op!(srgb_to_hsv, $data),
-            (Space::CIELAB, Space::CIELCH)
-            | (Space::OKLAB, Space::OKLCH)
-            | (Space::JZAZBZ, Space::JZCZHZ) => $op!(lab_to_lch, $data),
+            (Space::SRGB, Space::HSV) => { $op!(srgb_to_hsv, $data); $recurse(Space::HSV, $to, $data) }
+            (Space::CIELAB, Space::CIELCH) => { $op!(lab_to_lch, $data); $recurse(Space::CIELCH, $to, $data) }
+            (Space::OKLAB, Space::OKLCH) => { $op!(lab_to_lch, $data); $recurse(Space::OKLCH, $to, $data) }
+            (Space::JZAZBZ, Space::JZCZHZ) => { $op!(lab_to_lch, $data); $recurse(Space::JZCZHZ, $to, $data) }
 
             // Reverse Endcaps
             (Space::HSV, _) => { $op!(hsv_to_srgb, $data); $recurse(Space::SRGB, $to, $data) }
@@ -696,9 +2804,10 @@ macro_rules! graph {
             // SRGB Up
             (Space::SRGB, _) => { $op!(srgb_to_lrgb, $data); $recurse(Space::LRGB, $to, $data) }
 
-            // LRGB Down
+            // LRGB Down. HSV is derived from gamma encoded sRGB so the transfer is required here, not redundant
             (Space::LRGB, Space::SRGB | Space::HSV) => { $op!(lrgb_to_srgb, $data); $recurse(Space::SRGB, $to, $data) }
             // LRGB Up
+            (Space::LRGB, Space::OKLAB | Space::OKLCH) => { $op!(lrgb_to_oklab, $data); $recurse(Space::OKLAB, $to, $data) }
             (Space::LRGB, _) => { $op!(lrgb_to_xyz, $data); $recurse(Space::XYZ, $to, $data) }
 
             // XYZ Down
@@ -709,6 +2818,7 @@ macro_rules! graph {
             (Space::XYZ, Space::JZAZBZ | Space::JZCZHZ) => { $op!(xyz_to_jzazbz, $data); $recurse(Space::JZAZBZ, $to, $data) }
 
             // LAB Down
+            (Space::OKLAB, Space::SRGB | Space::LRGB | Space::HSV) => { $op!(oklab_to_lrgb, $data); $recurse(Space::LRGB, $to, $data) }
             (Space::CIELAB, _) => { $op!(cielab_to_xyz, $data); $recurse(Space::XYZ, $to, $data) }
             (Space::OKLAB, _) => { $op!(oklab_to_xyz, $data); $recurse(Space::XYZ, $to, $data) }
             (Space::JZAZBZ, _) => { $op!(jzazbz_to_xyz, $data); $recurse(Space::XYZ, $to, $data) }
@@ -716,24 +2826,266 @@ macro_rules! graph {
     };
 }
 
+/// Convert an sRGB color to `space` and return only `channel` of it, such as OKLAB L or OKLCH hue.
+///
+/// # Panics
+/// If `channel` is not 0, 1, or 2.
+pub fn extract_channel<T: DType>(srgb: &[T; 3], space: Space, channel: usize) -> T {
+    let mut pixel = *srgb;
+    convert_space::<T, 3>(Space::SRGB, space, &mut pixel);
+    pixel[channel]
+}
+
+/// Whether converting between `from` and `to` leaves the sRGB transfer function unapplied.
+///
+/// True when both spaces are gamma encoded (sRGB, HSV) or both are linear light derived.
+/// Routes never apply and then immediately undo the transfer, so when this is false it is applied exactly once.
+pub const fn is_transfer_identity(from: Space, to: Space) -> bool {
+    matches!(from, Space::SRGB | Space::HSV) == matches!(to, Space::SRGB | Space::HSV)
+}
+
 /// Runs conversion functions to convert `pixel` from one `Space` to another
 /// in the least possible moves.
+///
+/// NaN channels, such as CSS `none` from `str2col`, are not sanitized and will spread into whichever channels
+/// are derived from them. Debug builds panic if any of the first three channels is infinite on input.
 pub fn convert_space<T: DType, const N: usize>(from: Space, to: Space, pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    graph!(convert_space, pixel, from, to, op_single);
+    debug_assert_no_inf(from, pixel);
+    convert_space_step(from, to, pixel);
+}
+
+/// Same as `convert_space` for RGBA, but with `gamma_alpha` set alpha also gets the sRGB transfer function
+/// applied whenever the route crosses between sRGB and linear light, for legacy assets with gamma encoded alpha.
+///
+/// With `gamma_alpha` unset this is plain `convert_space`, which never touches alpha.
+pub fn convert_space_alpha<T: DType>(from: Space, to: Space, pixel: &mut [T; 4], gamma_alpha: bool) {
+    convert_space(from, to, pixel);
+    if gamma_alpha && !is_transfer_identity(from, to) {
+        pixel[3] = if matches!(from, Space::SRGB | Space::HSV) {
+            srgb_eotf(pixel[3])
+        } else {
+            srgb_oetf(pixel[3])
+        }
+    }
+}
+
+/// Same as `convert_space` but returns whether `pixel` was transformed at all,
+/// so caching layers can skip marking buffers dirty.
+///
+/// Every route between two different spaces does work, so this is only false when `from == to`.
+pub fn convert_space_if_needed<T: DType, const N: usize>(from: Space, to: Space, pixel: &mut [T; N]) -> bool
+where
+    Channels<N>: ValidChannels,
+{
+    if from == to {
+        return false;
+    }
+    convert_space(from, to, pixel);
+    true
+}
+
+fn convert_space_step<T: DType, const N: usize>(from: Space, to: Space, pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    graph!(convert_space_step, pixel, from, to, op_single);
+}
+
+/// Panics in debug builds if the color channels of `pixel` are infinite
+/// so bad input is caught before conversion rather than after. NaN is allowed as a missing component.
+#[inline]
+fn debug_assert_no_inf<T: DType, const N: usize>(space: Space, pixel: &[T; N]) {
+    debug_assert!(
+        pixel.iter().take(3).all(|c| c.is_finite() || c.is_nan()),
+        "infinite {} pixel passed to conversion: {:?}",
+        space,
+        pixel
+    );
+}
+
+fn convert_space_traced_step<T: DType, const N: usize, F: FnMut(Space, &[T; N])>(
+    from: Space,
+    to: Space,
+    data: &mut (&mut [T; N], &mut F),
+) where
+    Channels<N>: ValidChannels,
+{
+    (data.1)(from, data.0);
+    graph!(convert_space_traced_step, data, from, to, op_traced);
+}
+
+/// Same as `convert_space` but calls `trace` with the current space and values after every conversion step.
+///
+/// Useful for finding exactly which step loses precision or goes non-finite.
+pub fn convert_space_traced<T: DType, const N: usize>(
+    from: Space,
+    to: Space,
+    pixel: &mut [T; N],
+    trace: &mut impl FnMut(Space, &[T; N]),
+) where
+    Channels<N>: ValidChannels,
+{
+    debug_assert_no_inf(from, pixel);
+    let data = &mut (pixel, trace);
+    graph!(convert_space_traced_step, data, from, to, op_traced);
+}
+
+/// Converts `pixel` from `from` into `through`, runs `f` on it, then converts it back to `from`.
+///
+/// Each leg takes the shortest route through the conversion graph, so an sRGB pixel edited in OKLCH
+/// only decodes and re-encodes the sRGB transfer once. Prefer this over separate `convert_space` calls
+/// through manually chosen intermediate spaces.
+///
+/// # Examples
+///
+/// ```
+/// use colcon::{roundtrip_through, Space};
+/// let mut pixel = [0.8f32, 0.3, 0.2];
+/// // lighten by 0.1 OKLAB L
+/// roundtrip_through(Space::SRGB, Space::OKLCH, &mut pixel, |lch| lch[0] += 0.1);
+/// assert!(pixel[0] > 0.8);
+/// ```
+pub fn roundtrip_through<T: DType, const N: usize, F>(from: Space, through: Space, pixel: &mut [T; N], f: F)
+where
+    Channels<N>: ValidChannels,
+    F: FnOnce(&mut [T; N]),
+{
+    convert_space(from, through, pixel);
+    f(pixel);
+    convert_space(through, from, pixel);
+}
+
+/// Largest error of any channel after converting an sRGB grid to `space` and back.
+///
+/// The grid samples `steps + 1` values from 0.0 to 1.0 along each channel.
+/// Useful for validating the accuracy of a space's forward and backward conversions.
+pub fn gamut_roundtrip_max_error<T: DType>(space: Space, steps: usize) -> T {
+    let steps = steps.max(1);
+    let value = |n: usize| T::ff32(n as f32 / steps as f32);
+    let mut max = T::ff32(0.0);
+    for r in 0..=steps {
+        for g in 0..=steps {
+            for b in 0..=steps {
+                let srgb = [value(r), value(g), value(b)];
+                let mut pixel = srgb;
+                convert_space::<T, 3>(Space::SRGB, space, &mut pixel);
+                convert_space::<T, 3>(space, Space::SRGB, &mut pixel);
+                max = pixel
+                    .iter()
+                    .zip(srgb.iter())
+                    .fold(max, |m, (a, b)| m.max((*a - *b).abs()));
+            }
+        }
+    }
+    max
+}
+
+/// Converts a 2 channel luma + alpha pixel from one `Space` to another.
+///
+/// Channel 0 is treated as a neutral gray along the lightness axis of each space:
+/// all of R, G and B, the V of HSV, the Y of CIE XYZ, or the L/Jz of the LAB and LCH spaces.
+/// Channel 1 is alpha and is left untouched.
+///
+/// Separate from `convert_space` as `Channels<2>` isn't a `ValidChannels`, so grayscale
+/// buffers can't accidentally reach the 3 channel conversion functions.
+pub fn convert_space_luma<T: DType>(from: Space, to: Space, pixel: &mut [T; 2]) {
+    let l = pixel[0];
+    let zero = T::ff32(0.0);
+    let mut gray = match from {
+        Space::SRGB | Space::LRGB => [l; 3],
+        Space::HSV => [zero, zero, l],
+        Space::XYZ => D65.map(|c| l * c.to_dt()),
+        Space::CIELAB | Space::CIELCH | Space::OKLAB | Space::OKLCH | Space::JZAZBZ | Space::JZCZHZ => [l, zero, zero],
+    };
+    convert_space::<T, 3>(from, to, &mut gray);
+    pixel[0] = match to {
+        Space::HSV => gray[2],
+        Space::XYZ => gray[1],
+        _ => gray[0],
+    };
+}
+
+/// Same as `convert_space` but CIE LAB and CIE LCH are referenced to `white` instead of D65.
+///
+/// All other spaces are unaffected. CIE XYZ is used as-is without chromatic adaptation,
+/// so only XYZ values which are already relative to `white` will become neutral.
+pub fn convert_space_wp<T: DType, const N: usize>(from: Space, to: Space, pixel: &mut [T; N], white: Illuminant)
+where
+    Channels<N>: ValidChannels,
+{
+    let cie = [Space::CIELAB, Space::CIELCH];
+    match (cie.contains(&from), cie.contains(&to)) {
+        (true, false) => {
+            convert_space(from, Space::CIELAB, pixel);
+            cielab_to_xyz_wp(pixel, white.xyz());
+            convert_space(Space::XYZ, to, pixel);
+        }
+        (false, true) => {
+            convert_space(from, Space::XYZ, pixel);
+            xyz_to_cielab_wp(pixel, white.xyz());
+            convert_space(Space::CIELAB, to, pixel);
+        }
+        // Within LAB/LCH or entirely outside of it the white point is irrelevant
+        (true, true) | (false, false) => convert_space(from, to, pixel),
+    }
 }
 
 /// Runs conversion functions to convert `pixel` from one `Space` to another
 /// in the least possible moves.
 ///
 /// Caches conversion graph for faster iteration.
+/// Debug builds panic if any input pixel is infinite, same as `convert_space`.
 pub fn convert_space_chunked<T: DType, const N: usize>(from: Space, to: Space, pixels: &mut [[T; N]])
 where
     Channels<N>: ValidChannels,
 {
-    graph!(convert_space_chunked, pixels, from, to, op_chunk);
+    #[cfg(debug_assertions)]
+    pixels.iter().for_each(|p| debug_assert_no_inf(from, p));
+    convert_space_chunked_step(from, to, pixels);
+}
+
+/// Same as `convert_space_chunked` but converts at most the first `max` pixels, returning how many it did.
+///
+/// For spreading a large conversion across frames, continue with `&mut pixels[done..]` until it returns 0.
+pub fn convert_space_chunked_limited<T: DType, const N: usize>(
+    from: Space,
+    to: Space,
+    pixels: &mut [[T; N]],
+    max: usize,
+) -> usize
+where
+    Channels<N>: ValidChannels,
+{
+    let count = max.min(pixels.len());
+    convert_space_chunked(from, to, &mut pixels[..count]);
+    count
+}
+
+fn convert_space_chunked_step<T: DType, const N: usize>(from: Space, to: Space, pixels: &mut [[T; N]])
+where
+    Channels<N>: ValidChannels,
+{
+    match (from, to) {
+        // Fused sRGB EOTF + matrix so each pixel is only loaded once for both stages
+        (Space::SRGB, Space::XYZ | Space::CIELAB | Space::CIELCH | Space::JZAZBZ | Space::JZCZHZ) => {
+            pixels.iter_mut().for_each(|pixel| {
+                srgb_to_lrgb(pixel);
+                lrgb_to_xyz(pixel)
+            });
+            convert_space_chunked_step(Space::XYZ, to, pixels)
+        }
+        (Space::SRGB, Space::OKLAB | Space::OKLCH) => {
+            pixels.iter_mut().for_each(|pixel| {
+                srgb_to_lrgb(pixel);
+                lrgb_to_oklab(pixel)
+            });
+            convert_space_chunked_step(Space::OKLAB, to, pixels)
+        }
+        _ => graph!(convert_space_chunked_step, pixels, from, to, op_chunk),
+    }
 }
 
 /// Runs conversion functions to convert `pixel` from one `Space` to another
@@ -755,18 +3107,246 @@ where
         };
         (array_slice, remainder)
     };
-    graph!(convert_space_chunked, mut_chunks, from, to, op_chunk);
+    convert_space_chunked(from, to, mut_chunks);
+}
+
+/// Converts `pixels` treated as `vec3`s spaced `stride` values apart, such as std140/std430 buffers
+/// where every `vec3` is padded out to 16 bytes with `stride = 4`.
+///
+/// Only the first 3 values of each stride are touched, so padding is left as-is.
+/// A trailing element shorter than `stride` is still converted if it holds at least 3 values,
+/// so tightly-sized buffers without padding after the last `vec3` work too.
+///
+/// Panics if `stride < 3`.
+pub fn convert_space_padded<T: DType>(from: Space, to: Space, pixels: &mut [T], stride: usize) {
+    assert!(stride >= 3, "vec3 stride must be at least 3, got {}", stride);
+    pixels
+        .chunks_mut(stride)
+        .filter_map(|chunk| chunk.get_mut(..3))
+        .for_each(|vec3| convert_space::<T, 3>(from, to, vec3.try_into().unwrap()));
+}
+
+/// Same as `convert_space_chunked` but reads `src` as one DType and converts in the precision of `dst`.
+///
+/// Useful for reading f32 pixels into an f64 accumulator.
+/// Only the first `min(src.len(), dst.len())` pixels are written.
+pub fn convert_space_cast<A: DType, B: DType, const N: usize>(
+    from: Space,
+    to: Space,
+    src: &[[A; N]],
+    dst: &mut [[B; N]],
+) where
+    Channels<N>: ValidChannels,
+{
+    let len = src.len().min(dst.len());
+    src.iter()
+        .zip(dst.iter_mut())
+        .for_each(|(a, b)| *b = a.map(|c| B::ff64(c.if64())));
+    convert_space_chunked(from, to, &mut dst[..len]);
+}
+
+/// Summary of converted values returned by `convert_space_chunked_stats`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ConversionStats<T: DType, const N: usize> {
+    /// Smallest finite value of each channel, or infinity if none were finite
+    pub min: [T; N],
+    /// Largest finite value of each channel, or negative infinity if none were finite
+    pub max: [T; N],
+    /// Mean of the finite values of each channel, or NaN if none were finite
+    pub mean: [T; N],
+    /// Number of pixels with at least one NaN or infinite channel after conversion
+    pub non_finite: usize,
+}
+
+/// Same as `convert_space_chunked` but also gathers the range and mean of each output channel
+/// and how many pixels went non-finite, as a quick health check of a conversion over real data.
+pub fn convert_space_chunked_stats<T: DType, const N: usize>(
+    from: Space,
+    to: Space,
+    pixels: &mut [[T; N]],
+) -> ConversionStats<T, N>
+where
+    Channels<N>: ValidChannels,
+{
+    convert_space_chunked(from, to, pixels);
+
+    let mut stats = ConversionStats {
+        min: [T::ff32(f32::INFINITY); N],
+        max: [T::ff32(f32::NEG_INFINITY); N],
+        mean: [T::ff32(0.0); N],
+        non_finite: 0,
+    };
+    let mut counts = [0usize; N];
+    for pixel in pixels.iter() {
+        if !pixel.iter().all(|c| c.is_finite()) {
+            stats.non_finite += 1
+        }
+        for (n, c) in pixel.iter().enumerate().filter(|(_, c)| c.is_finite()) {
+            stats.min[n] = stats.min[n].min(*c);
+            stats.max[n] = stats.max[n].max(*c);
+            stats.mean[n] = stats.mean[n] + *c;
+            counts[n] += 1;
+        }
+    }
+    stats
+        .mean
+        .iter_mut()
+        .zip(counts)
+        .for_each(|(m, n)| *m = *m / T::ff32(n as f32));
+    stats
+}
+
+/// Collects the conversion functions from one `Space` to another in order
+fn conversion_route<T: DType, const N: usize>(from: Space, to: Space, route: &mut Vec<fn(&mut [T; N])>)
+where
+    Channels<N>: ValidChannels,
+{
+    graph!(conversion_route, route, from, to, op_push);
+}
+
+/// Iterator adapter created by `convert_space_iter`
+pub struct ConvertSpaceIter<I, T, const N: usize> {
+    iter: I,
+    from: Space,
+    route: Vec<fn(&mut [T; N])>,
+}
+
+impl<I, T: DType, const N: usize> Iterator for ConvertSpaceIter<I, T, N>
+where
+    I: Iterator<Item = [T; N]>,
+    Channels<N>: ValidChannels,
+{
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|mut pixel| {
+            debug_assert_no_inf(self.from, &pixel);
+            self.route.iter().for_each(|f| f(&mut pixel));
+            pixel
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Lazily converts each pixel of `pixels` from one `Space` to another
+/// in the least possible moves.
+///
+/// The conversion graph is resolved once when the adapter is created.
+/// Debug builds panic on infinite pixels as they're reached, same as `convert_space`.
+pub fn convert_space_iter<T: DType, const N: usize, I>(
+    from: Space,
+    to: Space,
+    pixels: I,
+) -> ConvertSpaceIter<I::IntoIter, T, N>
+where
+    I: IntoIterator<Item = [T; N]>,
+    Channels<N>: ValidChannels,
+{
+    let mut route = Vec::new();
+    conversion_route(from, to, &mut route);
+    ConvertSpaceIter {
+        iter: pixels.into_iter(),
+        from,
+        route,
+    }
+}
+
+/// Same as `convert_space_sliced` but with FFI types.
+///
+/// Returns 0 on success, 1 on invalid `from`, 2 on invalid `to`, 3 on invalid `pixels`
+///
+/// `len` is in elements rather than bytes
+pub fn convert_space_ffi<T: DType, const N: usize>(
+    from: *const c_char,
+    to: *const c_char,
+    pixels: *mut T,
+    len: usize,
+) -> i32
+where
+    Channels<N>: ValidChannels,
+{
+    let Ok(from) = Space::try_from(from) else { return 1 };
+    let Ok(to) = Space::try_from(to) else { return 2 };
+    let pixels = unsafe {
+        if pixels.is_null() {
+            return 3;
+        } else {
+            core::slice::from_raw_parts_mut(pixels, len)
+        }
+    };
+    convert_space_sliced::<T, N>(from, to, pixels);
+    0
+}
+
+/// Converts 8 bit pixels from one `Space` to another, treating 0..=255 as 0.0..=1.0 and working in f32.
+///
+/// Results are rounded and clamped back into 0..=255 like `srgb_to_irgb`, so both spaces must live in 0.0..=1.0
+/// as `Space::is_bounded` reports, namely sRGB, LRGB, and HSV. Other spaces are an error and leave `pixels` untouched.
+pub fn convert_space_irgb<const N: usize>(from: Space, to: Space, pixels: &mut [[u8; N]]) -> Result<(), String>
+where
+    Channels<N>: ValidChannels,
+{
+    if let Some(space) = [from, to].into_iter().find(|s| !s.is_bounded()) {
+        return Err(format!("{} does not fit in 8 bits", space));
+    }
+    let mut float: Vec<[f32; N]> = pixels.iter().map(|p| irgb_to_srgb(*p)).collect();
+    convert_space_chunked(from, to, &mut float);
+    pixels.iter_mut().zip(float).for_each(|(p, f)| *p = srgb_to_irgb(f));
+    Ok(())
+}
+
+/// Same as `convert_space_irgb` but with FFI types and the channel count picked at runtime.
+///
+/// Returns 0 on success, 1 on invalid `from`, 2 on invalid `to`, 3 on invalid `pixels`, 4 on `channels` not 3 or 4,
+/// 5 on either space not being bounded to 0.0..=1.0
+///
+/// `len` is in elements rather than pixels, and remainder values are ignored.
+///
+/// # Safety
+/// `from` and `to` must be null or point to nul terminated strings.
+/// `pixels` must be null or valid for reads and writes of `len` bytes.
+pub unsafe fn convert_space_u8_ffi(
+    from: *const c_char,
+    to: *const c_char,
+    pixels: *mut u8,
+    len: usize,
+    channels: usize,
+) -> i32 {
+    let Ok(from) = Space::try_from(from) else { return 1 };
+    let Ok(to) = Space::try_from(to) else { return 2 };
+    if pixels.is_null() {
+        return 3;
+    }
+    match channels {
+        3 => convert_space_irgb::<3>(from, to, unsafe {
+            core::slice::from_raw_parts_mut(pixels.cast(), len / 3)
+        }),
+        4 => convert_space_irgb::<4>(from, to, unsafe {
+            core::slice::from_raw_parts_mut(pixels.cast(), len / 4)
+        }),
+        _ => return 4,
+    }
+    .map_or(5, |_| 0)
 }
 
-/// Same as `convert_space_sliced` but with FFI types.
+/// Same as `convert_space_ffi` but leaves `src` untouched, writing the converted pixels into `dst` instead.
 ///
-/// Returns 0 on success, 1 on invalid `from`, 2 on invalid `to`, 3 on invalid `pixels`
+/// Returns 0 on success, 1 on invalid `from`, 2 on invalid `to`, 3 on invalid `src`, 4 on invalid `dst`
 ///
-/// `len` is in elements rather than bytes
-pub fn convert_space_ffi<T: DType, const N: usize>(
+/// `len` is in elements rather than bytes and `dst` must be at least `len` long.
+/// `src` and `dst` must not overlap.
+///
+/// # Safety
+/// `from` and `to` must be null or point to nul terminated strings.
+/// `src` must be null or valid for reads of `len` elements and `dst` null or valid for writes of `len` elements.
+pub unsafe fn convert_space_copy_ffi<T: DType, const N: usize>(
     from: *const c_char,
     to: *const c_char,
-    pixels: *mut T,
+    src: *const T,
+    dst: *mut T,
     len: usize,
 ) -> i32
 where
@@ -774,19 +3354,207 @@ where
 {
     let Ok(from) = Space::try_from(from) else { return 1 };
     let Ok(to) = Space::try_from(to) else { return 2 };
+    if src.is_null() {
+        return 3;
+    }
     let pixels = unsafe {
-        if pixels.is_null() {
-            return 3;
+        if dst.is_null() {
+            return 4;
         } else {
-            core::slice::from_raw_parts_mut(pixels, len)
+            core::ptr::copy_nonoverlapping(src, dst, len);
+            core::slice::from_raw_parts_mut(dst, len)
         }
     };
     convert_space_sliced::<T, N>(from, to, pixels);
     0
 }
 
+/// Shortcut to convert an sRGB color to OKLCH.
+///
+/// ```
+/// let [l, c, h] = colcon::to_oklch([1.0f32, 0.0, 0.0]);
+/// assert!((l - 0.628).abs() < 1e-3 && (c - 0.258).abs() < 1e-3 && (h - 29.23).abs() < 1e-2);
+/// ```
+pub fn to_oklch<T: DType>(mut srgb: [T; 3]) -> [T; 3] {
+    convert_space(Space::SRGB, Space::OKLCH, &mut srgb);
+    srgb
+}
+
+/// Shortcut to convert an sRGB color to Hue Saturation Lightness.
+///
+/// There's no HSL `Space`, this is `srgb_to_hsl`. Hue is 0.0..1.0 like `Space::HSV`.
+///
+/// ```
+/// assert_eq!(colcon::to_hsl([1.0f32, 0.0, 0.0]), [0.0, 1.0, 0.5]);
+/// assert_eq!(colcon::to_hsl([0.5f32, 0.5, 0.5]), [0.0, 0.0, 0.5]);
+/// ```
+pub fn to_hsl<T: DType>(mut srgb: [T; 3]) -> [T; 3] {
+    srgb_to_hsl(&mut srgb);
+    srgb
+}
+
+/// Shortcut to convert an sRGB color to linear light RGB.
+///
+/// ```
+/// let [r, g, b] = colcon::to_linear([1.0f32, 0.5, 0.0]);
+/// assert!(r == 1.0 && (g - 0.214).abs() < 1e-3 && b == 0.0);
+/// ```
+pub fn to_linear<T: DType>(mut srgb: [T; 3]) -> [T; 3] {
+    convert_space(Space::SRGB, Space::LRGB, &mut srgb);
+    srgb
+}
+
 // ### Convert Space ### }}}
 
+// ### LUT ### {{{
+
+/// Practical range of each channel of `space` for sRGB content, used by `generate_lut` and `posterize`
+fn channel_domain(space: Space) -> [[f32; 3]; 2] {
+    match space {
+        Space::SRGB | Space::LRGB | Space::HSV => [[0.0; 3], [1.0; 3]],
+        _ => {
+            let quants = space.srgb_quants();
+            // polar hues are unbounded in the quantiles
+            let finite = |q: f32, hue: f32| if q.is_finite() { q } else { hue };
+            [quants[0].map(|q| finite(q, 0.0)), quants[100].map(|q| finite(q, 360.0))]
+        }
+    }
+}
+
+/// Build a `size`³ 3D lookup table converting `from` to `to`, in `.cube` order with the first channel changing fastest.
+///
+/// RGB and HSV inputs are sampled over 0.0..=1.0. Other spaces are sampled between the extremes of their
+/// `srgb_quants`, with hues over 0.0..=360.0. `size` is raised to at least 2.
+pub fn generate_lut<T: DType>(from: Space, to: Space, size: usize) -> Vec<[T; 3]> {
+    let size = size.max(2);
+    let [min, max] = channel_domain(from);
+    let at = |c: usize, n: usize| T::ff32(min[c] + (max[c] - min[c]) * n as f32 / (size - 1) as f32);
+    let mut lut: Vec<[T; 3]> = (0..size.pow(3))
+        .map(|i| [at(0, i % size), at(1, i / size % size), at(2, i / size / size)])
+        .collect();
+    convert_space_chunked(from, to, &mut lut);
+    lut
+}
+
+/// Format a LUT from `generate_lut` as an Adobe/Resolve `.cube` file.
+///
+/// `from` must match the space given to `generate_lut` so the `DOMAIN_MIN`/`DOMAIN_MAX` lines are correct.
+pub fn write_cube_lut<T: DType>(lut: &[[T; 3]], from: Space) -> String {
+    let size = (lut.len() as f64).cbrt().round() as usize;
+    let [min, max] = channel_domain(from);
+    let mut cube = format!(
+        "LUT_3D_SIZE {}\nDOMAIN_MIN {} {} {}\nDOMAIN_MAX {} {} {}\n",
+        size, min[0], min[1], min[2], max[0], max[1], max[2]
+    );
+    lut.iter()
+        .for_each(|p| cube.push_str(&format!("{} {} {}\n", p[0], p[1], p[2])));
+    cube
+}
+
+/// A parsed `.cube` 3D lookup table
+#[derive(Clone, PartialEq, Debug)]
+pub struct CubeLut {
+    /// Samples per channel
+    pub size: usize,
+    /// Input value mapped to the first sample of each channel
+    pub domain_min: [f32; 3],
+    /// Input value mapped to the last sample of each channel
+    pub domain_max: [f32; 3],
+    /// `size`³ output values with the first channel changing fastest
+    pub table: Vec<[f32; 3]>,
+}
+
+impl CubeLut {
+    /// Read an Adobe/Resolve `.cube` 3D LUT, such as one from `write_cube_lut`.
+    ///
+    /// `TITLE` and `#` comments are ignored. `DOMAIN_MIN` and `DOMAIN_MAX` default to 0.0 and 1.0.
+    /// 1D LUTs are not supported.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lut = CubeLut {
+            size: 0,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            table: Vec::new(),
+        };
+        let triplet = |values: &str| -> Result<[f32; 3], String> {
+            let mut iter = values.split_whitespace().map(|v| v.parse::<f32>());
+            match (iter.next(), iter.next(), iter.next(), iter.next()) {
+                (Some(Ok(a)), Some(Ok(b)), Some(Ok(c)), None) => Ok([a, b, c]),
+                _ => Err(format!("Invalid LUT values `{}`", values)),
+            }
+        };
+        for line in text.lines().map(str::trim) {
+            let (key, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            match key {
+                "" | "TITLE" => (),
+                _ if key.starts_with('#') => (),
+                "LUT_1D_SIZE" => return Err(String::from("1D LUTs are not supported")),
+                "LUT_3D_SIZE" => {
+                    lut.size = rest
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("Invalid LUT_3D_SIZE `{}`", rest.trim()))?
+                }
+                "DOMAIN_MIN" => lut.domain_min = triplet(rest)?,
+                "DOMAIN_MAX" => lut.domain_max = triplet(rest)?,
+                _ => lut.table.push(triplet(line)?),
+            }
+        }
+        if lut.size < 2 {
+            Err(String::from("Missing or too small LUT_3D_SIZE"))
+        } else if lut.table.len() != lut.size.pow(3) {
+            Err(format!(
+                "Expected {} LUT values, found {}",
+                lut.size.pow(3),
+                lut.table.len()
+            ))
+        } else {
+            Ok(lut)
+        }
+    }
+}
+
+/// Map a color through `lut` with trilinear interpolation.
+/// Inputs outside of the LUT's domain are clamped to its edges.
+/// A LUT with under 2 samples per channel, which `CubeLut::parse` rejects, leaves the color unchanged.
+pub fn apply_cube_lut<T: DType>(srgb: &mut [T; 3], lut: &CubeLut) {
+    if lut.size < 2 {
+        return;
+    }
+    let last = lut.size - 1;
+    // lower sample index and fractional position towards the next one for each channel
+    let coords: [(usize, T); 3] = [0, 1, 2].map(|c| {
+        let (min, max) = (lut.domain_min[c], lut.domain_max[c]);
+        let pos = ((srgb[c] - min.to_dt()) / (max - min).to_dt() * T::ff32(last as f32))
+            .max(0.0.to_dt())
+            .min(T::ff32(last as f32));
+        let lower = (pos.trunc().if64() as usize).min(last - 1);
+        (lower, pos - T::ff32(lower as f32))
+    });
+    let sample = |r: usize, g: usize, b: usize| lut.table[r + g * lut.size + b * lut.size * lut.size];
+
+    let mut result = [T::ff32(0.0); 3];
+    for corner in 0..8 {
+        let offsets = [corner & 1, corner >> 1 & 1, corner >> 2 & 1];
+        let weight = (0..3).fold(T::ff32(1.0), |w, c| {
+            let f = coords[c].1;
+            w * if offsets[c] == 1 { f } else { T::ff32(1.0) - f }
+        });
+        let value = sample(
+            coords[0].0 + offsets[0],
+            coords[1].0 + offsets[1],
+            coords[2].0 + offsets[2],
+        );
+        result
+            .iter_mut()
+            .zip(value)
+            .for_each(|(r, v)| *r = T::ff32(v).fma(weight, *r));
+    }
+    *srgb = result;
+}
+
+// ### LUT ### }}}
+
 // ### Str2Col ### {{{
 fn rm_paren<'a>(s: &'a str) -> &'a str {
     if let (Some(f), Some(l)) = (s.chars().next(), s.chars().last()) {
@@ -797,12 +3565,88 @@ fn rm_paren<'a>(s: &'a str) -> &'a str {
     s
 }
 
+/// CSS extends its RGB transfer functions below zero by mirroring them
+fn css_signed<T: DType>(n: T, f: impl Fn(T) -> T) -> T {
+    if n < T::ff32(0.0) {
+        -f(-n)
+    } else {
+        f(n)
+    }
+}
+
+/// `bt709_eotf` with the CSS `rec2020` constants
+fn css_rec2020_eotf<T: DType>(n: T) -> T {
+    if n < T::ff64(CSS_REC2020_BETA * 4.5) {
+        n / 4.5.to_dt()
+    } else {
+        ((n + T::ff64(CSS_REC2020_ALPHA - 1.0)) / T::ff64(CSS_REC2020_ALPHA)).powf(T::ff64(1.0 / BT709_GAMMA))
+    }
+}
+
+/// Parses the inside of a CSS `color()` function, such as `display-p3 1 0 0 / 0.5`.
+///
+/// Identifiers without a matching `Space` are converted into one that can hold them losslessly.
+fn css_color_fn<T: DType, const N: usize>(s: &str) -> Option<(Space, [T; N])>
+where
+    Channels<N>: ValidChannels,
+{
+    let (id, values) = s.trim().split_once(char::is_whitespace)?;
+    // No space prefix means the values parse with sRGB's 0..1 percentages, which all of these share
+    let values = values.replace('/', " ");
+    // Only bare components, no nested space names or hex codes
+    let first = values.split_whitespace().next()?;
+    if !(first.eq_ignore_ascii_case("none") || first.starts_with(|c: char| c.is_ascii_digit() || "+-.".contains(c))) {
+        return None;
+    }
+    let (_, mut pixel) = str2col::<T, N>(&values)?;
+    let space = match id.to_ascii_lowercase().as_str() {
+        "srgb" => Space::SRGB,
+        "srgb-linear" => Space::LRGB,
+        "xyz" | "xyz-d65" => Space::XYZ,
+        "display-p3" => {
+            pixel.iter_mut().take(3).for_each(|c| *c = css_signed(*c, srgb_eotf));
+            lp3_to_lrgb(&mut pixel);
+            Space::LRGB
+        }
+        "rec2020" => {
+            pixel
+                .iter_mut()
+                .take(3)
+                .for_each(|c| *c = css_signed(*c, css_rec2020_eotf));
+            lrec2020_to_lrgb(&mut pixel);
+            Space::LRGB
+        }
+        "xyz-d50" => {
+            // Bradford chromatic adaptation to D65
+            let [src, dst] = [D50, D65].map(|w| mm(LMS_BRADFORD, w.map(|c| c as f64)));
+            xyz_to_lms(&mut pixel, LmsMatrix::Bradford);
+            pixel
+                .iter_mut()
+                .take(3)
+                .enumerate()
+                .for_each(|(n, c)| *c = *c * T::ff64(dst[n] / src[n]));
+            lms_to_xyz(&mut pixel, LmsMatrix::Bradford);
+            Space::XYZ
+        }
+        _ => return None,
+    };
+    Some((space, pixel))
+}
+
 /// Convert a string into a space/array combo.
 /// Separated with spaces, ';', ':', or ','
 ///
 /// Can additionally be set as a % of SDR range.
 ///
-/// Alpha will be NaN if only 3 values are provided.
+/// Alpha will be NaN if only 3 values are provided, including 6 digit hex codes.
+///
+/// Like CSS, any component may be `none` or `nan` to mark it as missing, which parses to NaN.
+/// NaN is carried through every conversion untouched, so it will spread into every channel derived from it.
+/// Only infinite input trips the debug check of `convert_space`, and `str2col` never returns that.
+///
+/// CSS `color(<space-id> ...)` is read for `srgb`, `srgb-linear`, `display-p3`, `rec2020`, `xyz`, `xyz-d65`, and `xyz-d50`.
+/// Those without a `Space` of their own are converted on read: `display-p3` and `rec2020` to `Space::LRGB`,
+/// `xyz-d50` to `Space::XYZ` by Bradford adaptation.
 ///
 /// # Examples
 ///
@@ -813,6 +3657,8 @@ fn rm_paren<'a>(s: &'a str) -> &'a str {
 /// assert_eq!(str2col("lch:50;20;120"), Some((Space::CIELCH, [50.0f32, 20.0, 120.0])));
 /// assert_eq!(str2col("oklab(0.2, 0.6, -0.5)"), Some((Space::OKLAB, [0.2f32, 0.6, -0.5])));
 /// assert_eq!(str2col("srgb 100% 50% 25%"), Some((Space::SRGB, [1.0f32, 0.5, 0.25])));
+/// assert!(str2col::<f32, 3>("oklch(0.6 none 120)").unwrap().1[1].is_nan());
+/// assert_eq!(str2col("color(srgb-linear 0.5 0.5 0.5)"), Some((Space::LRGB, [0.5f32, 0.5, 0.5])));
 /// ```
 pub fn str2col<T: DType, const N: usize>(mut s: &str) -> Option<(Space, [T; N])>
 where
@@ -821,10 +3667,25 @@ where
     s = rm_paren(s.trim());
     let mut space = Space::SRGB;
     let mut result = [f32::NAN; N];
+    let mut count = 0;
 
     // Return hex if valid
-    if let Ok(irgb) = hex_to_irgb(s) {
-        return Some((space, irgb_to_srgb(irgb)));
+    if let Ok(irgb) = hex_to_irgb::<N>(s) {
+        let mut pixel = irgb_to_srgb::<T, N>(irgb);
+        // 6 digits has no alpha, so match the unspecified alpha of other formats
+        if N == 4 && s.trim_start_matches('#').len() == 6 {
+            pixel[3] = T::ff32(f32::NAN)
+        }
+        return Some((space, pixel));
+    }
+
+    // CSS `color(<space-id> ...)`
+    if let Some(inner) = s
+        .get(..6)
+        .filter(|f| f.eq_ignore_ascii_case("color("))
+        .and(s.strip_suffix(')'))
+    {
+        return css_color_fn(&inner[6..]);
     }
 
     let seps = [',', ':', ';'];
@@ -843,15 +3704,18 @@ where
         .filter(|s| !s.is_empty())
         .enumerate()
     {
+        count = n + 1;
         if n > 3 {
             return None;
         } else if n >= result.len() {
             continue;
+        } else if split.eq_ignore_ascii_case("none") || split.eq_ignore_ascii_case("nan") {
+            result[n] = f32::NAN;
         } else if let Ok(value) = split.parse::<f32>() {
             result[n] = value;
         } else if split.ends_with('%') {
             if let Ok(percent) = split[0..(split.len() - 1)].parse::<f32>() {
-                // alpha
+                // alpha is always 0..1 regardless of space, so never consult the quantiles
                 if n == 3 {
                     result[n] = percent / 100.0;
                     continue;
@@ -873,7 +3737,7 @@ where
             return None;
         }
     }
-    if result.iter().take(3).all(|v| v.is_finite()) {
+    if count >= 3 && result.iter().take(3).all(|v| !v.is_infinite()) {
         Some((space, result.map(|c| c.to_dt())))
     } else {
         None
@@ -882,119 +3746,417 @@ where
 
 /// Convert a string into a pixel of the requested Space.
 ///
-/// Shorthand for str2col() -> convert_space()
-pub fn str2space<T: DType, const N: usize>(s: &str, to: Space) -> Option<[T; N]>
+/// Shorthand for str2col() -> convert_space()
+///
+/// Missing `none` components are carried through the conversion as NaN.
+pub fn str2space<T: DType, const N: usize>(s: &str, to: Space) -> Option<[T; N]>
+where
+    Channels<N>: ValidChannels,
+{
+    str2col(s).map(|(from, mut col)| {
+        convert_space(from, to, &mut col);
+        col
+    })
+}
+
+/// Same as `str2space` but with FFI types
+///
+/// Returns an N-length pointer to T on success or null on failure
+pub fn str2space_ffi<T: DType, const N: usize>(s: *const c_char, to: *const c_char) -> *const T
+where
+    Channels<N>: ValidChannels,
+{
+    if s.is_null() {
+        return core::ptr::null();
+    };
+    let Some(s) = unsafe { CStr::from_ptr(s) }.to_str().ok() else {
+        return core::ptr::null();
+    };
+    let Ok(to) = Space::try_from(to) else {
+        return core::ptr::null();
+    };
+    str2space::<T, N>(s, to).map_or(core::ptr::null(), |b| Box::into_raw(Box::new(b)).cast())
+}
+
+/// Format an sRGB pixel as a CSS `color(display-p3 ...)` string that `str2col` can read back.
+///
+/// Alpha is written after a `/` if present and not NaN.
+pub fn srgb_to_css_p3<T: DType, const N: usize>(mut pixel: [T; N]) -> String
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = srgb_eotf(*c));
+    lrgb_to_lp3(&mut pixel);
+    pixel.iter_mut().take(3).for_each(|c| *c = css_signed(*c, srgb_oetf));
+    let mut result = format!("color(display-p3 {} {} {}", pixel[0], pixel[1], pixel[2]);
+    if N == 4 && !pixel[3].is_nan() {
+        result += &format!(" / {}", pixel[3]);
+    }
+    result + ")"
+}
+
+/// Parses the first 3 whitespace separated integers of a palette line
+fn palette_line(line: &str) -> Option<[u8; 3]> {
+    let mut values = line.split_whitespace().map(|v| v.parse::<u8>().ok());
+    Some([values.next()??, values.next()??, values.next()??])
+}
+
+/// Read the colors of a GIMP `.gpl` palette as integer RGB.
+///
+/// Header, `Name:`/`Columns:` metadata, `#` comments, and malformed lines are skipped
+/// as they don't start with 3 integers. Trailing color names are ignored.
+pub fn parse_gpl(text: &str) -> Vec<[u8; 3]> {
+    text.lines().filter_map(palette_line).collect()
+}
+
+/// Read the colors of a JASC `.pal` palette as integer RGB.
+///
+/// Returns nothing if the `JASC-PAL` header is missing. Reads at most as many colors
+/// as the header's count and skips malformed lines.
+pub fn parse_jasc_pal(text: &str) -> Vec<[u8; 3]> {
+    let mut lines = text.lines().map(str::trim);
+    if lines.next() != Some("JASC-PAL") {
+        return Vec::new();
+    }
+    let _version = lines.next();
+    let count = lines.next().and_then(|c| c.parse::<usize>().ok()).unwrap_or(0);
+    lines.filter_map(palette_line).take(count).collect()
+}
+
+// ### Str2Col ### }}}
+
+// ### FORWARD ### {{{
+
+/// Convert floating (0.0..1.0) RGB to integer (0..255) RGB.
+pub fn srgb_to_irgb<const N: usize>(pixel: [f32; N]) -> [u8; N]
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.map(|c| (c * 255.0).round().clamp(0.0, 255.0) as u8)
+}
+
+/// Same as `srgb_to_irgb` but also returns whether each channel was outside of 0.0..1.0 before clamping.
+pub fn srgb_to_irgb_clip<const N: usize>(pixel: [f32; N]) -> ([u8; N], [bool; N])
+where
+    Channels<N>: ValidChannels,
+{
+    (srgb_to_irgb(pixel), pixel.map(|c| !(0.0..=1.0).contains(&c)))
+}
+
+/// Convert floating (0.0..1.0) RGB to integers of any `bits` depth, such as 10 bit for HDR10 or 16 bit for PNG.
+///
+/// Scales by `2^bits - 1` with rounding and clamping like `srgb_to_irgb`.
+///
+/// # Panics
+/// If `bits` is not within 1..=32.
+pub fn srgb_to_int<const N: usize>(pixel: [f32; N], bits: u32) -> [u32; N]
+where
+    Channels<N>: ValidChannels,
+{
+    assert!((1..=32).contains(&bits), "bit depth must be 1 to 32, got {}", bits);
+    let max = 2f64.powi(bits as i32) - 1.0;
+    pixel.map(|c| (c as f64 * max).round().clamp(0.0, max) as u32)
+}
+
+/// Convert floating Linear Light RGB to integer (0..255) sRGB, encoding the color channels with `srgb_oetf` first.
+///
+/// Alpha is never gamma encoded so it is quantized as-is.
+pub fn lrgb_to_irgb<const N: usize>(mut pixel: [f32; N]) -> [u8; N]
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = srgb_oetf(*c));
+    srgb_to_irgb(pixel)
+}
+
+/// Largest jump of any channel between neighboring pixels of an integer RGB ramp.
+///
+/// A smooth 8 bit gradient steps by at most 1, so larger values indicate visible banding.
+pub fn max_banding<const N: usize>(gradient: &[[u8; N]]) -> u8
+where
+    Channels<N>: ValidChannels,
+{
+    gradient
+        .windows(2)
+        .flat_map(|w| w[0].iter().zip(w[1].iter()).map(|(a, b)| a.abs_diff(*b)))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Create a hexadecimal string from integer RGB.
+/// Alpha is written last as in CSS `#RRGGBBAA`, see `irgb_to_hex_argb` for alpha first.
+pub fn irgb_to_hex<const N: usize>(pixel: [u8; N]) -> String
+where
+    Channels<N>: ValidChannels,
+{
+    let mut hex = String::with_capacity(N * 2 + 1);
+    push_hex(&mut hex, pixel);
+    hex
+}
+
+/// Create an alpha first `#AARRGGBB` hexadecimal string from integer RGBA, as used by Android and .NET.
+///
+/// The pixel itself is still in RGBA order, only the string is rotated.
+pub fn irgb_to_hex_argb(pixel: [u8; 4]) -> String {
+    let [r, g, b, a] = pixel;
+    irgb_to_hex([a, r, g, b])
+}
+
+/// Create one string of hexadecimal codes from a slice of integer RGB, joined by `separator`.
+///
+/// Builds into a single allocation, useful for writing out palettes.
+pub fn irgb_slice_to_hex<const N: usize>(pixels: &[[u8; N]], separator: &str) -> String
+where
+    Channels<N>: ValidChannels,
+{
+    let mut hex = String::with_capacity((N * 2 + 1 + separator.len()) * pixels.len());
+    pixels.iter().enumerate().for_each(|(i, pixel)| {
+        if i > 0 {
+            hex.push_str(separator)
+        }
+        push_hex(&mut hex, *pixel)
+    });
+    hex
+}
+
+fn push_hex<const N: usize>(hex: &mut String, pixel: [u8; N]) {
+    hex.push('#');
+
+    pixel.into_iter().for_each(|c| {
+        [c / 16, c % 16]
+            .into_iter()
+            .for_each(|n| hex.push(if n >= 10 { n + 55 } else { n + 48 } as char))
+    });
+}
+
+/// Hue from 0.0..1.0 and chroma of gamma encoded RGB, followed by its largest and smallest channels.
+///
+/// Shared by the HSV, HSL and HWB conversions so they always agree on hue. Grays have a hue of 0.
+fn rgb_to_hue_chroma<T: DType>(rgb: [T; 3]) -> [T; 4] {
+    let [r, g, b] = rgb;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let sextant = if chroma == 0.0.to_dt() {
+        0.0.to_dt()
+    } else if r == max {
+        (g - b) / chroma
+    } else if g == max {
+        (b - r) / chroma + 2.0.to_dt()
+    } else {
+        (r - g) / chroma + 4.0.to_dt()
+    };
+    [wrap_hue(sextant / 6.0.to_dt(), 1.0.to_dt()), chroma, max, min]
+}
+
+/// Inverse of `rgb_to_hue_chroma`, giving RGB with its smallest channel at 0.0 to be offset by the caller.
+fn hue_chroma_to_rgb<T: DType>(hue: T, chroma: T) -> [T; 3] {
+    if chroma == 0.0.to_dt() {
+        return [0.0.to_dt(); 3];
+    }
+    let h = wrap_hue(hue, 1.0.to_dt()) * 6.0.to_dt();
+    let sextant = h.trunc();
+    let rise = chroma * (h - sextant);
+    let fall = chroma - rise;
+    let zero = 0.0.to_dt();
+
+    if sextant == 0.0.to_dt() {
+        [chroma, rise, zero]
+    } else if sextant == 1.0.to_dt() {
+        [fall, chroma, zero]
+    } else if sextant == 2.0.to_dt() {
+        [zero, chroma, rise]
+    } else if sextant == 3.0.to_dt() {
+        [zero, fall, chroma]
+    } else if sextant == 4.0.to_dt() {
+        [rise, zero, chroma]
+    } else {
+        [chroma, zero, fall]
+    }
+}
+
+/// Convert from sRGB to HSV.
+pub fn srgb_to_hsv<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let [h, c, v, _] = rgb_to_hue_chroma([pixel[0], pixel[1], pixel[2]]);
+    let s = if c == 0.0.to_dt() { c } else { c / v };
+    [pixel[0], pixel[1], pixel[2]] = [h, s, v];
+}
+
+/// Convert from sRGB to Hue Saturation Lightness, with hue 0.0..1.0 like HSV.
+///
+/// There's no HSL `Space`, so use `hsl_to_srgb` to come back.
+pub fn srgb_to_hsl<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let [h, _, max, min] = rgb_to_hue_chroma([pixel[0], pixel[1], pixel[2]]);
+    let l = (max + min) / 2.0.to_dt();
+    let s = if l <= 0.0.to_dt() || l >= 1.0.to_dt() {
+        0.0.to_dt()
+    } else {
+        (max - l) / l.min(T::ff32(1.0) - l)
+    };
+    [pixel[0], pixel[1], pixel[2]] = [h, s, l];
+}
+
+/// Convert from sRGB to Hue Whiteness Blackness, with hue 0.0..1.0 like HSV.
+///
+/// There's no HWB `Space`, so use `hwb_to_srgb` to come back.
+///
+/// <https://www.w3.org/TR/css-color-4/#the-hwb-notation>
+pub fn srgb_to_hwb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let [h, _, max, min] = rgb_to_hue_chroma([pixel[0], pixel[1], pixel[2]]);
+    [pixel[0], pixel[1], pixel[2]] = [h, min, T::ff32(1.0) - max];
+}
+
+/// Same as `srgb_to_hsv` but grayscale colors take `previous_hue` instead of 0,
+/// so a hue slider doesn't snap to red while desaturating through gray.
+pub fn srgb_to_hsv_keep_hue<T: DType, const N: usize>(pixel: &mut [T; N], previous_hue: T)
+where
+    Channels<N>: ValidChannels,
+{
+    srgb_to_hsv(pixel);
+    if pixel[1] == 0.0.to_dt() {
+        pixel[0] = previous_hue
+    }
+}
+
+/// Convert from sRGB to HSV with hue in degrees 0..360 instead of 0..1.
+pub fn srgb_to_hsv_deg<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    srgb_to_hsv(pixel);
+    pixel[0] = pixel[0] * 360.0.to_dt();
+}
+
+/// Convert from sRGB to Linear RGB by applying the sRGB EOTF
+///
+/// <https://www.color.org/chardata/rgb/srgb.xalter>
+pub fn srgb_to_lrgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = srgb_eotf(*c));
+}
+
+/// Convert from Linear Light RGB to CIE XYZ, D65 standard illuminant
+///
+/// Uses the 4 decimal IEC 61966-2-1 matrix, so white lands slightly off `D65`.
+/// See `lrgb_to_xyz_precise` for the matrix derived from the primaries.
+///
+/// <https://en.wikipedia.org/wiki/SRGB#From_sRGB_to_CIE_XYZ>
+pub fn lrgb_to_xyz<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    str2col(s).map(|(from, mut col)| {
-        convert_space(from, to, &mut col);
-        col
-    })
+    [pixel[0], pixel[1], pixel[2]] = mm(XYZ65_MAT, [pixel[0], pixel[1], pixel[2]])
 }
 
-/// Same as `str2space` but with FFI types
-///
-/// Returns an N-length pointer to T on success or null on failure
-pub fn str2space_ffi<T: DType, const N: usize>(s: *const c_char, to: *const c_char) -> *const T
+/// Same as `lrgb_to_xyz` but converts four pixels per call through a channel-major batched matrix multiply.
+/// Benchmark against plain `lrgb_to_xyz` on your target before preferring it.
+pub fn lrgb_to_xyz_x4<T: DType, const N: usize>(pixels: &mut [[T; N]; 4])
 where
     Channels<N>: ValidChannels,
 {
-    if s.is_null() {
-        return core::ptr::null();
-    };
-    let Some(s) = unsafe { CStr::from_ptr(s) }.to_str().ok() else {
-        return core::ptr::null();
-    };
-    let Ok(to) = Space::try_from(to) else {
-        return core::ptr::null();
-    };
-    str2space::<T, N>(s, to).map_or(core::ptr::null(), |b| Box::into_raw(Box::new(b)).cast())
+    let batch = [0, 1, 2].map(|c| pixels.each_ref().map(|p| p[c]));
+    mm4(XYZ65_MAT, batch)
+        .iter()
+        .enumerate()
+        .for_each(|(c, channel)| pixels.iter_mut().zip(channel).for_each(|(p, v)| p[c] = *v));
 }
-// ### Str2Col ### }}}
 
-// ### FORWARD ### {{{
+/// Same as `lrgb_to_xyz` but evaluable at compile time, for converting fixed colors into `const` items.
+///
+/// Only purely linear conversions are available as `const fn`, since the transfer functions
+/// and perceptual spaces depend on `powf` which cannot run at compile time.
+///
+/// # Examples
+///
+/// ```
+/// const WHITE: [f32; 3] = colcon::lrgb_to_xyz_const([1.0; 3]);
+/// assert!((WHITE[1] - 1.0).abs() < 1e-6);
+/// ```
+pub const fn lrgb_to_xyz_const(pixel: [f32; 3]) -> [f32; 3] {
+    mm_const(XYZ65_MAT, pixel)
+}
 
-/// Convert floating (0.0..1.0) RGB to integer (0..255) RGB.
-pub fn srgb_to_irgb<const N: usize>(pixel: [f32; N]) -> [u8; N]
+/// Convert from Linear Light RGB to CIE XYZ, D65 standard illuminant
+///
+/// Uses the full precision matrix derived from the sRGB primaries instead of the rounded IEC 61966-2-1 matrix,
+/// so white lands exactly on `D65` and relative luminance survives a round trip through `xyz_to_lrgb_precise`.
+/// Results differ from `lrgb_to_xyz` by roughly 1e-4.
+///
+/// <https://en.wikipedia.org/wiki/SRGB#Primaries>
+pub fn lrgb_to_xyz_precise<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    pixel.map(|c| ((c * 255.0).round().max(0.0).min(255.0) as u8))
+    [pixel[0], pixel[1], pixel[2]] = mm(XYZ65_MAT_PRECISE, [pixel[0], pixel[1], pixel[2]])
 }
 
-/// Create a hexadecimal string from integer RGB.
-pub fn irgb_to_hex<const N: usize>(pixel: [u8; N]) -> String
+/// Convert from Linear Light RGB to OKLAB, skipping the intermediate CIE XYZ step.
+///
+/// Equivalent to `lrgb_to_xyz` followed by `xyz_to_oklab` with one less matrix multiply.
+///
+/// <https://bottosson.github.io/posts/oklab/>
+pub fn lrgb_to_oklab<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    let mut hex = String::with_capacity(N * 2 + 1);
-    hex.push('#');
-
-    pixel.into_iter().for_each(|c| {
-        [c / 16, c % 16]
-            .into_iter()
-            .for_each(|n| hex.push(if n >= 10 { n + 55 } else { n + 48 } as char))
-    });
-
-    hex
+    let mut lms = mm(LRGB_TO_LMS, [pixel[0], pixel[1], pixel[2]]);
+    lms.iter_mut().for_each(|c| *c = c.scbrt());
+    [pixel[0], pixel[1], pixel[2]] = mm(OKLAB_M2, lms);
 }
 
-/// Convert from sRGB to HSV.
-pub fn srgb_to_hsv<T: DType, const N: usize>(pixel: &mut [T; N])
+/// Convert from Linear Light RGB to Linear Light Display P3 in a single matrix multiply.
+///
+/// sRGB is fully contained by P3 so in-gamut inputs always land in gamut.
+pub fn lrgb_to_lp3<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    let vmin = pixel[0].min(pixel[1]).min(pixel[2]);
-    let vmax = pixel[0].max(pixel[1]).max(pixel[2]);
-    let dmax = vmax - vmin;
-
-    let v = vmax;
-
-    let (h, s): (T, T) = if dmax == 0.0.to_dt() {
-        (0.0.to_dt(), 0.0.to_dt())
-    } else {
-        let s = dmax / vmax;
-
-        let [branch_0, branch_1] = [pixel[0] == vmax, pixel[1] == vmax];
-
-        pixel.iter_mut().take(3).for_each(|c| {
-            *c = (((vmax - *c) / 6.0.to_dt()) + (dmax / 2.0.to_dt())) / dmax;
-        });
+    [pixel[0], pixel[1], pixel[2]] = mm(LRGB_TO_LP3, [pixel[0], pixel[1], pixel[2]]);
+}
 
-        let h = if branch_0 {
-            pixel[2] - pixel[1]
-        } else if branch_1 {
-            T::ff32(1.0 / 3.0) + pixel[0] - pixel[2]
-        } else {
-            T::ff32(2.0 / 3.0) + pixel[1] - pixel[0]
-        }
-        .rem_euclid(1.0.to_dt());
-        (h, s)
-    };
-    pixel[0] = h;
-    pixel[1] = s;
-    pixel[2] = v;
+/// Convert from Linear Light RGB to Linear Light Rec. 2020 in a single matrix multiply.
+///
+/// Neither P3 nor Rec. 2020 are a `Space`, so pair these gamut functions by hand
+/// rather than routing through `convert_space` and CIE XYZ.
+pub fn lrgb_to_lrec2020<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = mm(LRGB_TO_LREC2020, [pixel[0], pixel[1], pixel[2]]);
 }
 
-/// Convert from sRGB to Linear RGB by applying the sRGB EOTF
+/// Convert from Linear Light Display P3 to Linear Light Rec. 2020 in a single matrix multiply.
 ///
-/// <https://www.color.org/chardata/rgb/srgb.xalter>
-pub fn srgb_to_lrgb<T: DType, const N: usize>(pixel: &mut [T; N])
+/// P3 is fully contained by Rec. 2020 so in-gamut inputs always land in gamut.
+pub fn lp3_to_lrec2020<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    pixel.iter_mut().take(3).for_each(|c| *c = srgb_eotf(*c));
+    [pixel[0], pixel[1], pixel[2]] = mm(LP3_TO_LREC2020, [pixel[0], pixel[1], pixel[2]]);
 }
 
-/// Convert from Linear Light RGB to CIE XYZ, D65 standard illuminant
+/// Convert from CIE XYZ to LMS cone responses using the given `matrix`.
 ///
-/// <https://en.wikipedia.org/wiki/SRGB#From_sRGB_to_CIE_XYZ>
-pub fn lrgb_to_xyz<T: DType, const N: usize>(pixel: &mut [T; N])
+/// `LmsMatrix::default()` is Hunt-Pointer-Estevez.
+///
+/// <https://en.wikipedia.org/wiki/LMS_color_space>
+pub fn xyz_to_lms<T: DType, const N: usize>(pixel: &mut [T; N], matrix: LmsMatrix)
 where
     Channels<N>: ValidChannels,
 {
-    [pixel[0], pixel[1], pixel[2]] = mm(XYZ65_MAT, [pixel[0], pixel[1], pixel[2]])
+    [pixel[0], pixel[1], pixel[2]] = mm(matrix.m(), [pixel[0], pixel[1], pixel[2]])
 }
 
 /// Convert from CIE XYZ to CIE LAB.
@@ -1004,8 +4166,24 @@ pub fn xyz_to_cielab<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    // Reverse D65 standard illuminant
-    pixel.iter_mut().take(3).zip(D65).for_each(|(c, d)| *c = *c / d.to_dt());
+    xyz_to_cielab_wp(pixel, D65)
+}
+
+/// Convert from CIE XYZ to CIE LAB referenced to an arbitrary `white` point.
+///
+/// No chromatic adaptation is performed.
+///
+/// <https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIEXYZ_to_CIELAB>
+pub fn xyz_to_cielab_wp<T: DType, const N: usize>(pixel: &mut [T; N], white: [f32; 3])
+where
+    Channels<N>: ValidChannels,
+{
+    // Reverse standard illuminant
+    pixel
+        .iter_mut()
+        .take(3)
+        .zip(white)
+        .for_each(|(c, d)| *c = *c / d.to_dt());
 
     pixel.iter_mut().take(3).for_each(|c| {
         if *c > T::ff32(LAB_DELTA).powi(3) {
@@ -1034,6 +4212,23 @@ where
     [pixel[0], pixel[1], pixel[2]] = mm(OKLAB_M2, lms);
 }
 
+/// Convert OKLAB to OKLrAB by replacing L with the toe corrected Lr estimate, leaving A and B untouched.
+/// Also works on OKLCH.
+///
+/// Lr is what Okhsl and Okhsv use for lightness, and sits closer to CIE L* for dark colors.
+/// CSS Color 4 `oklab()` and `oklch()` use plain L, as does `Space::OKLAB` everywhere else in colcon,
+/// so convert back with `oklrab_to_oklab` before comparing against CSS values.
+///
+/// <https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab>
+pub fn oklab_to_oklrab<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let x = T::ff64(OKLAB_TOE_K3) * pixel[0] - T::ff64(OKLAB_TOE_K1);
+    let root = x.fma(x, T::ff64(4.0 * OKLAB_TOE_K2 * OKLAB_TOE_K3) * pixel[0]).sqrt();
+    pixel[0] = (x + root) / 2.0.to_dt();
+}
+
 /// Convert CIE XYZ to JzAzBz
 ///
 /// <https://opg.optica.org/oe/fulltext.cfm?uri=oe-25-13-15131>
@@ -1041,162 +4236,395 @@ pub fn xyz_to_jzazbz<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    let mut lms = mm(
-        JZAZBZ_M1,
-        [
-            pixel[0].fma(JZAZBZ_B.to_dt(), T::ff32(-JZAZBZ_B + 1.0) * pixel[2]),
-            pixel[1].fma(JZAZBZ_G.to_dt(), T::ff32(-JZAZBZ_G + 1.0) * pixel[0]),
-            pixel[2],
-        ],
-    );
+    let mut lms = mm(
+        JZAZBZ_M1,
+        [
+            pixel[0].fma(T::ff64(JZAZBZ_B), T::ff64(-JZAZBZ_B + 1.0) * pixel[2]),
+            pixel[1].fma(T::ff64(JZAZBZ_G), T::ff64(-JZAZBZ_G + 1.0) * pixel[0]),
+            pixel[2],
+        ],
+    );
+
+    lms.iter_mut().for_each(|e| *e = pqz_oetf(*e));
+
+    let lab = mm(JZAZBZ_M2, lms);
+
+    pixel[0] = (T::ff64(1.0 + JZAZBZ_D) * lab[0]) / lab[0].fma(T::ff64(JZAZBZ_D), 1.0.to_dt()) - T::ff64(JZAZBZ_D0);
+    pixel[1] = lab[1];
+    pixel[2] = lab[2];
+}
+
+/// Multiply JzAzBz by `factor` so the channels occupy a friendlier numeric range.
+/// Raw Jz values are tiny, with sRGB primaries landing near 0.01, which wastes f32 precision in downstream math.
+///
+/// This is opt-in and never applied by `convert_space`; undo it with `jzazbz_unscale` before converting back.
+/// Works on JzCzHz as well if applied before `lab_to_lch`, as chroma scales linearly with Az and Bz.
+///
+/// `JZAZBZ_SCALE` is a reasonable default factor.
+pub fn jzazbz_scale<T: DType, const N: usize>(pixel: &mut [T; N], factor: T)
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = *c * factor);
+}
+
+/// Scale relative CIE XYZ, where diffuse white has Y = 1.0, to absolute luminance in cd/m²
+/// with diffuse white at `diffuse_white_nits`. 203 nits is the BT.2408 reference white for HDR.
+///
+/// JzAzBz and ICtCp are built on PQ which expects absolute luminance.
+/// `convert_space` feeds them relative XYZ as-is, effectively a 1 nit diffuse white,
+/// so scale before calling `xyz_to_jzazbz` directly when absolute values matter.
+pub fn scale_to_nits<T: DType, const N: usize>(pixel: &mut [T; N], diffuse_white_nits: T)
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = *c * diffuse_white_nits);
+}
+
+// Disabled for now as all the papers are paywalled
+// /// Convert CIE XYZ to CAM16-UCS
+// #[no_mangle]
+// pub extern "C" fn xyz_to_cam16ucs(pixel: &mut [f32; 3]) {
+
+// }
+
+/// Convert LRGB to ICtCp. Unvalidated, WIP
+///
+/// <https://www.itu.int/rec/R-REC-BT.2100/en>
+pub fn _lrgb_to_ictcp<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    // <https://www.itu.int/rec/R-REC-BT.2020/en>
+    // let alpha = 1.09929682680944;
+    // let beta = 0.018053968510807;
+    // let bt2020 = |e: &mut f32| {
+    //     *e = if *e < beta {4.5 * *e}
+    //     else {alpha * e.powf(0.45) - (alpha - 1.0)}
+    // };
+    // pixel.iter_mut().for_each(|c| bt2020(c));
+
+    let mut lms = mm(ICTCP_M1, [pixel[0], pixel[1], pixel[2]]);
+    // lms prime
+    lms.iter_mut().for_each(|c| *c = pq_oetf(*c));
+    [pixel[0], pixel[1], pixel[2]] = mm(ICTCP_M2, lms);
+}
+
+/// `rem_euclid` into the half-open `0.0..period`.
+///
+/// `rem_euclid` alone rounds tiny negatives up to exactly `period`, so those are folded to zero.
+fn wrap_hue<T: DType>(hue: T, period: T) -> T {
+    let hue = hue.rem_euclid(period);
+    if hue >= period {
+        0.0.to_dt()
+    } else {
+        hue
+    }
+}
+
+/// Converts an LAB based space to a cylindrical representation.
+///
+/// Hue is in degrees within `0.0..360.0`, never exactly 360.
+///
+/// <https://en.wikipedia.org/wiki/CIELAB_color_space#Cylindrical_model>
+pub fn lab_to_lch<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = [
+        pixel[0],
+        (pixel[1].powi(2) + pixel[2].powi(2)).sqrt(),
+        wrap_hue(pixel[2].atan2(pixel[1]).to_degrees(), 360.0.to_dt()),
+    ];
+}
+
+/// Same as `lab_to_lch` but near-neutral colors take `hint` as their hue instead of whatever `atan2` lands on,
+/// useful for keeping hue stable through grays when interpolating.
+///
+/// A color counts as neutral when its chroma is within 1% of its lightness.
+/// The threshold is relative because the matrices leave a small residual chroma on grays
+/// that varies by orders of magnitude between CIELAB, OKLAB, and JzAzBz.
+pub fn lab_to_lch_hue_hint<T: DType, const N: usize>(pixel: &mut [T; N], hint: T)
+where
+    Channels<N>: ValidChannels,
+{
+    lab_to_lch(pixel);
+    if pixel[1] <= pixel[0].abs() * 0.01.to_dt() {
+        pixel[2] = hint
+    }
+}
+
+// ### FORWARD ### }}}
+
+// ### BACKWARD ### {{{
+
+/// Convert integer (0..255) RGB to floating (0.0..1.0) RGB.
+pub fn irgb_to_srgb<T: DType, const N: usize>(pixel: [u8; N]) -> [T; N]
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.map(|c| T::ff32(c as f32 / 255.0))
+}
+
+/// Convert integers of any `bits` depth to floating (0.0..1.0) RGB. Inverse of `srgb_to_int`.
+///
+/// Values above `2^bits - 1` are not clamped.
+///
+/// # Panics
+/// If `bits` is not within 1..=32.
+pub fn int_to_srgb<T: DType, const N: usize>(pixel: [u32; N], bits: u32) -> [T; N]
+where
+    Channels<N>: ValidChannels,
+{
+    assert!((1..=32).contains(&bits), "bit depth must be 1 to 32, got {}", bits);
+    let max = 2f64.powi(bits as i32) - 1.0;
+    pixel.map(|c| T::ff64(c as f64 / max))
+}
+
+/// Convert integer (0..255) sRGB to floating Linear Light RGB. Alpha is left linear.
+pub fn irgb_to_lrgb<T: DType, const N: usize>(pixel: [u8; N]) -> [T; N]
+where
+    Channels<N>: ValidChannels,
+{
+    let mut pixel = irgb_to_srgb::<T, N>(pixel);
+    pixel.iter_mut().take(3).for_each(|c| *c = srgb_eotf(*c));
+    pixel
+}
+
+/// Reasons a hex string failed to parse
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HexError {
+    /// Number of hex digits is not one of the accepted lengths
+    BadLength(usize),
+    /// Character is not a hexadecimal digit
+    BadChar(char),
+    /// No hex digits at all. Displays the same as `BadLength(0)` to keep the `String` API messages unchanged
+    Empty,
+}
+
+impl Display for HexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HexError::BadLength(n) => write!(f, "Incorrect hex length {}", n),
+            HexError::BadChar(c) => write!(f, "Hex character '{}' out of bounds", c),
+            HexError::Empty => f.write_str("Incorrect hex length 0"),
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+/// Value of a single hexadecimal digit
+fn hex_nibble(c: char) -> Result<u32, HexError> {
+    let u = c as u32;
+    // numeric
+    if 57 >= u && u >= 48 {
+        Ok(u - 48)
+    // uppercase
+    } else if 70 >= u && u >= 65 {
+        Ok(u - 55)
+    // lowercase
+    } else if 102 >= u && u >= 97 {
+        Ok(u - 87)
+    } else {
+        Err(HexError::BadChar(c))
+    }
+}
+
+/// Hex digit values of `hex` if it has one of the `lengths`, ignoring surrounding whitespace and a leading '#'
+fn hex_nibbles(hex: &str, lengths: [usize; 2]) -> Result<Vec<u32>, HexError> {
+    let mut chars = hex.trim().chars();
+    if chars.as_str().starts_with('#') {
+        chars.next();
+    }
+
+    match chars.as_str().len() {
+        0 => Err(HexError::Empty),
+        n if lengths.contains(&n) => chars.map(hex_nibble).collect(),
+        n => Err(HexError::BadLength(n)),
+    }
+}
+
+/// Create integer RGB set from hex string.
+/// `DEFAULT` is only used when 4 channels are requested but 3 is given.
+pub fn hex_to_irgb_default_e<const N: usize, const DEFAULT: u8>(hex: &str) -> Result<[u8; N], HexError>
+where
+    Channels<N>: ValidChannels,
+{
+    let ids = hex_nibbles(hex, [6, 8])?;
+
+    let mut result = [DEFAULT; N];
+
+    ids.chunks(2)
+        .take(result.len())
+        .enumerate()
+        .for_each(|(n, chunk)| result[n] = ((chunk[0]) * 16 + chunk[1]) as u8);
+
+    Ok(result)
+}
+
+/// Create integer RGB set from hex string.
+/// `DEFAULT` is only used when 4 channels are requested but 3 is given.
+///
+/// Same as `hex_to_irgb_default_e` with the error formatted as a `String`.
+pub fn hex_to_irgb_default<const N: usize, const DEFAULT: u8>(hex: &str) -> Result<[u8; N], String>
+where
+    Channels<N>: ValidChannels,
+{
+    hex_to_irgb_default_e::<N, DEFAULT>(hex).map_err(|e| e.to_string())
+}
+
+/// Create 16 bit integer RGB set from a deep color hex string such as `#RRRRGGGGBBBB` or `#RRRRGGGGBBBBAAAA`.
+/// `DEFAULT` is only used when 4 channels are requested but 3 is given.
+pub fn hex_to_irgb16_default_e<const N: usize, const DEFAULT: u16>(hex: &str) -> Result<[u16; N], HexError>
+where
+    Channels<N>: ValidChannels,
+{
+    let ids = hex_nibbles(hex, [12, 16])?;
 
-    lms.iter_mut().for_each(|e| *e = pqz_oetf(*e));
+    let mut result = [DEFAULT; N];
 
-    let lab = mm(JZAZBZ_M2, lms);
+    ids.chunks(4)
+        .take(result.len())
+        .enumerate()
+        .for_each(|(n, chunk)| result[n] = chunk.iter().fold(0, |acc, c| acc * 16 + c) as u16);
 
-    pixel[0] = (T::ff32(1.0 + JZAZBZ_D) * lab[0]) / lab[0].fma(JZAZBZ_D.to_dt(), 1.0.to_dt()) - JZAZBZ_D0.to_dt();
-    pixel[1] = lab[1];
-    pixel[2] = lab[2];
+    Ok(result)
 }
 
-// Disabled for now as all the papers are paywalled
-// /// Convert CIE XYZ to CAM16-UCS
-// #[no_mangle]
-// pub extern "C" fn xyz_to_cam16ucs(pixel: &mut [f32; 3]) {
-
-// }
-
-/// Convert LRGB to ICtCp. Unvalidated, WIP
+/// Create 16 bit integer RGB set from a deep color hex string such as `#RRRRGGGGBBBB` or `#RRRRGGGGBBBBAAAA`.
+/// `DEFAULT` is only used when 4 channels are requested but 3 is given.
 ///
-/// <https://www.itu.int/rec/R-REC-BT.2100/en>
-pub fn _lrgb_to_ictcp<T: DType, const N: usize>(pixel: &mut [T; N])
+/// Same as `hex_to_irgb16_default_e` with the error formatted as a `String`.
+pub fn hex_to_irgb16_default<const N: usize, const DEFAULT: u16>(hex: &str) -> Result<[u16; N], String>
 where
     Channels<N>: ValidChannels,
 {
-    // <https://www.itu.int/rec/R-REC-BT.2020/en>
-    // let alpha = 1.09929682680944;
-    // let beta = 0.018053968510807;
-    // let bt2020 = |e: &mut f32| {
-    //     *e = if *e < beta {4.5 * *e}
-    //     else {alpha * e.powf(0.45) - (alpha - 1.0)}
-    // };
-    // pixel.iter_mut().for_each(|c| bt2020(c));
-
-    let mut lms = mm(ICTCP_M1, [pixel[0], pixel[1], pixel[2]]);
-    // lms prime
-    lms.iter_mut().for_each(|c| *c = pq_oetf(*c));
-    [pixel[0], pixel[1], pixel[2]] = mm(ICTCP_M2, lms);
+    hex_to_irgb16_default_e::<N, DEFAULT>(hex).map_err(|e| e.to_string())
 }
 
-/// Converts an LAB based space to a cylindrical representation.
-///
-/// <https://en.wikipedia.org/wiki/CIELAB_color_space#Cylindrical_model>
-pub fn lab_to_lch<T: DType, const N: usize>(pixel: &mut [T; N])
+/// Create 16 bit integer RGB set from a deep color hex string.
+/// Will default to 65535 for alpha if 4 channels requested but hex length is 12.
+/// Use `hex_to_irgb16_default` to customize this.
+pub fn hex_to_irgb16_e<const N: usize>(hex: &str) -> Result<[u16; N], HexError>
 where
     Channels<N>: ValidChannels,
 {
-    [pixel[0], pixel[1], pixel[2]] = [
-        pixel[0],
-        (pixel[1].powi(2) + pixel[2].powi(2)).sqrt(),
-        pixel[2].atan2(pixel[1]).to_degrees().rem_euclid(360.0.to_dt()),
-    ];
+    hex_to_irgb16_default_e::<N, { u16::MAX }>(hex)
 }
 
-// ### FORWARD ### }}}
-
-// ### BACKWARD ### {{{
+/// Create 16 bit integer RGB set from a deep color hex string.
+/// Will default to 65535 for alpha if 4 channels requested but hex length is 12.
+/// Use `hex_to_irgb16_default` to customize this.
+pub fn hex_to_irgb16<const N: usize>(hex: &str) -> Result<[u16; N], String>
+where
+    Channels<N>: ValidChannels,
+{
+    hex_to_irgb16_default::<N, { u16::MAX }>(hex)
+}
 
-/// Convert integer (0..255) RGB to floating (0.0..1.0) RGB.
-pub fn irgb_to_srgb<T: DType, const N: usize>(pixel: [u8; N]) -> [T; N]
+/// Create integer RGB set from hex string.
+/// Will default to 255 for alpha if 4 channels requested but hex length is 6.
+/// Use `hex_to_irgb_default_e` to customize this.
+pub fn hex_to_irgb_e<const N: usize>(hex: &str) -> Result<[u8; N], HexError>
 where
     Channels<N>: ValidChannels,
 {
-    pixel.map(|c| T::ff32(c as f32 / 255.0))
+    hex_to_irgb_default_e::<N, 255>(hex)
 }
 
 /// Create integer RGB set from hex string.
-/// `DEFAULT` is only used when 4 channels are requested but 3 is given.
-pub fn hex_to_irgb_default<const N: usize, const DEFAULT: u8>(hex: &str) -> Result<[u8; N], String>
+/// Will default to 255 for alpha if 4 channels requested but hex length is 6.
+/// Use `hex_to_irgb_default` to customize this.
+///
+/// 8 digit hex is read as CSS `#RRGGBBAA`. For alpha first `#AARRGGBB` use `hex_to_irgb_argb`.
+pub fn hex_to_irgb<const N: usize>(hex: &str) -> Result<[u8; N], String>
 where
     Channels<N>: ValidChannels,
 {
-    let mut chars = hex.trim().chars();
-    if chars.as_str().starts_with('#') {
-        chars.next();
-    }
+    hex_to_irgb_default::<N, 255>(hex)
+}
 
-    let ids: Vec<u32> = match chars.as_str().len() {
-        6 | 8 => chars
-            .map(|c| {
-                let u = c as u32;
-                // numeric
-                if 57 >= u && u >= 48 {
-                    Ok(u - 48)
-                // uppercase
-                } else if 70 >= u && u >= 65 {
-                    Ok(u - 55)
-                // lowercase
-                } else if 102 >= u && u >= 97 {
-                    Ok(u - 87)
-                } else {
-                    Err(String::from("Hex character '") + &String::from(c) + "' out of bounds")
-                }
-            })
-            .collect(),
-        n => Err(String::from("Incorrect hex length ") + &n.to_string()),
-    }?;
+/// Create integer RGBA from an alpha first `#AARRGGBB` hex string, as used by Android and .NET.
+/// 6 digit hex is read as `#RRGGBB` with an alpha of 255.
+///
+/// The result is in the usual RGBA order, so `"#80FF0000"` is half transparent red
+/// where `hex_to_irgb` would read it as a fully transparent green `[0x80, 0xFF, 0x00, 0x00]`.
+pub fn hex_to_irgb_argb_e(hex: &str) -> Result<[u8; 4], HexError> {
+    let ids = hex_nibbles(hex, [6, 8])?;
 
-    let mut result = [DEFAULT; N];
+    let mut result = [255; 4];
 
     ids.chunks(2)
-        .take(result.len())
         .enumerate()
         .for_each(|(n, chunk)| result[n] = ((chunk[0]) * 16 + chunk[1]) as u8);
 
+    if ids.len() == 8 {
+        result.rotate_left(1)
+    }
+
     Ok(result)
 }
 
-/// Create integer RGB set from hex string.
-/// Will default to 255 for alpha if 4 channels requested but hex length is 6.
-/// Use `hex_to_irgb_default` to customize this.
-pub fn hex_to_irgb<const N: usize>(hex: &str) -> Result<[u8; N], String>
+/// Create integer RGBA from an alpha first `#AARRGGBB` hex string, as used by Android and .NET.
+/// 6 digit hex is read as `#RRGGBB` with an alpha of 255.
+///
+/// Same as `hex_to_irgb_argb_e` with the error formatted as a `String`.
+pub fn hex_to_irgb_argb(hex: &str) -> Result<[u8; 4], String> {
+    hex_to_irgb_argb_e(hex).map_err(|e| e.to_string())
+}
+
+/// Convert from HSV to sRGB.
+///
+/// Only hue is normalized, wrapping around 0.0..1.0. Value scales the output linearly, so V above 1.0
+/// gives HDR sRGB above 1.0. Saturation is extrapolated rather than clamped: above 1.0 pushes the
+/// smallest channel below zero and negative values mirror it, matching what `srgb_to_hsv` produces
+/// for out of range sRGB so those round trip.
+pub fn hsv_to_srgb<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    hex_to_irgb_default::<N, 255>(hex)
+    let c = pixel[2] * pixel[1];
+    let m = pixel[2] - c;
+    [pixel[0], pixel[1], pixel[2]] = hue_chroma_to_rgb(pixel[0], c).map(|x| x + m);
 }
 
-/// Convert from HSV to sRGB.
-pub fn hsv_to_srgb<T: DType, const N: usize>(pixel: &mut [T; N])
+/// Convert from Hue Saturation Lightness to sRGB, with hue wrapping around 0.0..1.0 like HSV.
+pub fn hsl_to_srgb<T: DType, const N: usize>(pixel: &mut [T; N])
 where
     Channels<N>: ValidChannels,
 {
-    if pixel[1] == 0.0.to_dt() {
-        [pixel[0], pixel[1]] = [pixel[2]; 2];
+    let l = pixel[2];
+    let c = pixel[1] * l.min(T::ff32(1.0) - l) * 2.0.to_dt();
+    let m = l - c / 2.0.to_dt();
+    [pixel[0], pixel[1], pixel[2]] = hue_chroma_to_rgb(pixel[0], c).map(|x| x + m);
+}
+
+/// Convert from Hue Whiteness Blackness to sRGB, with hue wrapping around 0.0..1.0 like HSV.
+///
+/// Whiteness and blackness summing past 1.0 are scaled down to fit, giving a gray.
+///
+/// <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+pub fn hwb_to_srgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let [w, b] = [pixel[1], pixel[2]];
+    let sum = w + b;
+    [pixel[0], pixel[1], pixel[2]] = if sum >= 1.0.to_dt() {
+        [w / sum; 3]
     } else {
-        let mut var_h = pixel[0] * 6.0.to_dt();
-        if var_h == 6.0.to_dt() {
-            var_h = 0.0.to_dt()
-        }
-        let var_i = var_h.trunc();
-        let var_1 = pixel[2] * (T::ff32(1.0) - pixel[1]);
-        let var_2 = pixel[2] * (-var_h + var_i).fma(pixel[1], 1.0.to_dt());
-        let var_3 = pixel[2] * (T::ff32(-1.0) + (var_h - var_i)).fma(pixel[1], T::ff32(1.0));
-
-        [pixel[0], pixel[1], pixel[2]] = if var_i == 0.0.to_dt() {
-            [pixel[2], var_3, var_1]
-        } else if var_i == 1.0.to_dt() {
-            [var_2, pixel[2], var_1]
-        } else if var_i == 2.0.to_dt() {
-            [var_1, pixel[2], var_3]
-        } else if var_i == 3.0.to_dt() {
-            [var_1, var_2, pixel[2]]
-        } else if var_i == 4.0.to_dt() {
-            [var_3, var_1, pixel[2]]
-        } else {
-            [pixel[2], var_1, var_2]
-        }
-    }
+        hue_chroma_to_rgb(pixel[0], T::ff32(1.0) - sum).map(|x| x + w)
+    };
+}
+
+/// Convert from HSV with hue in degrees 0..360 to sRGB.
+pub fn hsv_deg_to_srgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    pixel[0] = pixel[0] / 360.0.to_dt();
+    hsv_to_srgb(pixel);
 }
 
 /// Convert from Linear RGB to sRGB by applying the inverse sRGB EOTF
@@ -1219,10 +4647,39 @@ where
     [pixel[0], pixel[1], pixel[2]] = mm(XYZ65_MAT_INV, [pixel[0], pixel[1], pixel[2]])
 }
 
+/// Same as `xyz_to_lrgb` but evaluable at compile time. See `lrgb_to_xyz_const`.
+pub const fn xyz_to_lrgb_const(pixel: [f32; 3]) -> [f32; 3] {
+    mm_const(XYZ65_MAT_INV, pixel)
+}
+
+/// Convert from CIE XYZ to Linear Light RGB.
+///
+/// Inverse of `lrgb_to_xyz_precise`
+///
+/// <https://en.wikipedia.org/wiki/SRGB#Primaries>
+pub fn xyz_to_lrgb_precise<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = mm(XYZ65_MAT_PRECISE_INV, [pixel[0], pixel[1], pixel[2]])
+}
+
 /// Convert from CIE LAB to CIE XYZ.
 ///
 /// <https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIELAB_to_CIEXYZ>
 pub fn cielab_to_xyz<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    cielab_to_xyz_wp(pixel, D65)
+}
+
+/// Convert from CIE LAB referenced to an arbitrary `white` point to CIE XYZ.
+///
+/// No chromatic adaptation is performed.
+///
+/// <https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIELAB_to_CIEXYZ>
+pub fn cielab_to_xyz_wp<T: DType, const N: usize>(pixel: &mut [T; N], white: [f32; 3])
 where
     Channels<N>: ValidChannels,
 {
@@ -1241,7 +4698,21 @@ where
         }
     });
 
-    pixel.iter_mut().take(3).zip(D65).for_each(|(c, d)| *c = *c * d.to_dt());
+    pixel
+        .iter_mut()
+        .take(3)
+        .zip(white)
+        .for_each(|(c, d)| *c = *c * d.to_dt());
+}
+
+/// Convert from LMS cone responses to CIE XYZ using the given `matrix`.
+///
+/// <https://en.wikipedia.org/wiki/LMS_color_space>
+pub fn lms_to_xyz<T: DType, const N: usize>(pixel: &mut [T; N], matrix: LmsMatrix)
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = mm(matrix.m_inv(), [pixel[0], pixel[1], pixel[2]])
 }
 
 /// Convert from OKLAB to CIE XYZ.
@@ -1256,6 +4727,77 @@ where
     [pixel[0], pixel[1], pixel[2]] = mm(OKLAB_M1_INV, lms);
 }
 
+/// Convert OKLrAB back to OKLAB, undoing the toe on L as used by CSS Color 4. Also works on OKLrCH.
+///
+/// <https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab>
+pub fn oklrab_to_oklab<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    pixel[0] = pixel[0].fma(pixel[0], T::ff64(OKLAB_TOE_K1) * pixel[0])
+        / (T::ff64(OKLAB_TOE_K3) * (pixel[0] + T::ff64(OKLAB_TOE_K2)));
+}
+
+/// Convert from OKLAB to Linear Light RGB, skipping the intermediate CIE XYZ step.
+///
+/// Equivalent to `oklab_to_xyz` followed by `xyz_to_lrgb` with one less matrix multiply.
+///
+/// <https://bottosson.github.io/posts/oklab/>
+pub fn oklab_to_lrgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    let mut lms = mm(OKLAB_M2_INV, [pixel[0], pixel[1], pixel[2]]);
+    lms.iter_mut().for_each(|c| *c = c.powi(3));
+    [pixel[0], pixel[1], pixel[2]] = mm(LMS_TO_LRGB, lms);
+}
+
+/// Convert from Linear Light Display P3 to Linear Light RGB in a single matrix multiply.
+///
+/// Colors outside of sRGB will be out of the 0..1 range.
+pub fn lp3_to_lrgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = mm(LP3_TO_LRGB, [pixel[0], pixel[1], pixel[2]]);
+}
+
+/// Convert from Linear Light Rec. 2020 to Linear Light RGB in a single matrix multiply.
+///
+/// Colors outside of sRGB will be out of the 0..1 range.
+pub fn lrec2020_to_lrgb<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = mm(LREC2020_TO_LRGB, [pixel[0], pixel[1], pixel[2]]);
+}
+
+/// Convert from Linear Light Rec. 2020 to Linear Light Display P3 in a single matrix multiply.
+///
+/// Colors outside of P3 will be out of the 0..1 range.
+pub fn lrec2020_to_lp3<T: DType, const N: usize>(pixel: &mut [T; N])
+where
+    Channels<N>: ValidChannels,
+{
+    [pixel[0], pixel[1], pixel[2]] = mm(LREC2020_TO_LP3, [pixel[0], pixel[1], pixel[2]]);
+}
+
+/// Reverses `scale_to_nits`, returning absolute CIE XYZ in cd/m² to relative XYZ where diffuse white has Y = 1.0.
+pub fn scale_from_nits<T: DType, const N: usize>(pixel: &mut [T; N], diffuse_white_nits: T)
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = *c / diffuse_white_nits);
+}
+
+/// Reverses `jzazbz_scale` with the same `factor`.
+pub fn jzazbz_unscale<T: DType, const N: usize>(pixel: &mut [T; N], factor: T)
+where
+    Channels<N>: ValidChannels,
+{
+    pixel.iter_mut().take(3).for_each(|c| *c = *c / factor);
+}
+
 /// Convert JzAzBz to CIE XYZ
 ///
 /// <https://opg.optica.org/oe/fulltext.cfm?uri=oe-25-13-15131>
@@ -1266,8 +4808,8 @@ where
     let mut lms = mm(
         JZAZBZ_M2_INV,
         [
-            (pixel[0] + JZAZBZ_D0.to_dt())
-                / (pixel[0] + JZAZBZ_D0.to_dt()).fma(T::ff32(-JZAZBZ_D), T::ff32(1.0 + JZAZBZ_D)),
+            (pixel[0] + T::ff64(JZAZBZ_D0))
+                / (pixel[0] + T::ff64(JZAZBZ_D0)).fma(T::ff64(-JZAZBZ_D), T::ff64(1.0 + JZAZBZ_D)),
             pixel[1],
             pixel[2],
         ],
@@ -1277,8 +4819,8 @@ where
 
     [pixel[0], pixel[1], pixel[2]] = mm(JZAZBZ_M1_INV, lms);
 
-    pixel[0] = pixel[2].fma((JZAZBZ_B - 1.0).to_dt(), pixel[0]) / JZAZBZ_B.to_dt();
-    pixel[1] = pixel[0].fma((JZAZBZ_G - 1.0).to_dt(), pixel[1]) / JZAZBZ_G.to_dt();
+    pixel[0] = pixel[2].fma(T::ff64(JZAZBZ_B - 1.0), pixel[0]) / T::ff64(JZAZBZ_B);
+    pixel[1] = pixel[0].fma(T::ff64(JZAZBZ_G - 1.0), pixel[1]) / T::ff64(JZAZBZ_G);
 }
 
 // Disabled for now as all the papers are paywalled
@@ -1319,6 +4861,44 @@ where
 
 // BACKWARD }}}
 
+// ### Bench ### {{{
+
+/// Times `convert_space_chunked` over a generated gradient buffer `pixels` long.
+///
+/// Lets downstream users profile their exact `from`, `to`, DType, and channel combination
+/// on their own hardware without copying the criterion harness.
+#[cfg(feature = "bench")]
+pub fn bench_convert<T: DType, const N: usize>(from: Space, to: Space, pixels: usize) -> std::time::Duration
+where
+    Channels<N>: ValidChannels,
+{
+    let mut buffer: Vec<[T; N]> = (0..pixels).map(|n| [T::ff32(n as f32 / pixels as f32); N]).collect();
+
+    let start = std::time::Instant::now();
+    convert_space_chunked(from, to, &mut buffer);
+    let elapsed = start.elapsed();
+
+    core::hint::black_box(buffer);
+    elapsed
+}
+
+/// Megapixels per second `convert_space_chunked` manages for 3 channel f32 on a fixed 2^20 pixel buffer,
+/// as the best of three runs.
+///
+/// A single number for asserting against in downstream regression tests.
+#[cfg(feature = "bench")]
+pub fn throughput_mpps(from: Space, to: Space) -> f64 {
+    const PIXELS: usize = 1 << 20;
+    let best = (0..3)
+        .map(|_| bench_convert::<f32, 3>(from, to, PIXELS))
+        .min()
+        .unwrap_or_default()
+        .max(std::time::Duration::from_nanos(1));
+    PIXELS as f64 / best.as_secs_f64() / 1e6
+}
+
+// ### Bench ### }}}
+
 // ### MONOTYPED EXTERNAL FUNCTIONS ### {{{
 
 #[no_mangle]
@@ -1338,6 +4918,75 @@ extern "C" fn convert_space_4f64(from: *const c_char, to: *const c_char, pixels:
     convert_space_ffi::<_, 4>(from, to, pixels, len)
 }
 
+#[no_mangle]
+extern "C" fn convert_space_copy_3f32(
+    from: *const c_char,
+    to: *const c_char,
+    src: *const f32,
+    dst: *mut f32,
+    len: usize,
+) -> i32 {
+    unsafe { convert_space_copy_ffi::<_, 3>(from, to, src, dst, len) }
+}
+#[no_mangle]
+extern "C" fn convert_space_copy_4f32(
+    from: *const c_char,
+    to: *const c_char,
+    src: *const f32,
+    dst: *mut f32,
+    len: usize,
+) -> i32 {
+    unsafe { convert_space_copy_ffi::<_, 4>(from, to, src, dst, len) }
+}
+#[no_mangle]
+extern "C" fn convert_space_copy_3f64(
+    from: *const c_char,
+    to: *const c_char,
+    src: *const f64,
+    dst: *mut f64,
+    len: usize,
+) -> i32 {
+    unsafe { convert_space_copy_ffi::<_, 3>(from, to, src, dst, len) }
+}
+#[no_mangle]
+extern "C" fn convert_space_copy_4f64(
+    from: *const c_char,
+    to: *const c_char,
+    src: *const f64,
+    dst: *mut f64,
+    len: usize,
+) -> i32 {
+    unsafe { convert_space_copy_ffi::<_, 4>(from, to, src, dst, len) }
+}
+
+#[no_mangle]
+extern "C" fn convert_space_u8(
+    from: *const c_char,
+    to: *const c_char,
+    pixels: *mut u8,
+    len: usize,
+    channels: usize,
+) -> i32 {
+    unsafe { convert_space_u8_ffi(from, to, pixels, len, channels) }
+}
+
+#[no_mangle]
+extern "C" fn hk_high2023_comp_slice_3f32(pixels: *mut f32, len: usize) -> i32 {
+    unsafe { hk_high2023_comp_ffi::<_, 3>(pixels, len) }
+}
+#[no_mangle]
+extern "C" fn hk_high2023_comp_slice_4f32(pixels: *mut f32, len: usize) -> i32 {
+    unsafe { hk_high2023_comp_ffi::<_, 4>(pixels, len) }
+}
+#[no_mangle]
+extern "C" fn hk_high2023_comp_slice_3f64(pixels: *mut f64, len: usize) -> i32 {
+    unsafe { hk_high2023_comp_ffi::<_, 3>(pixels, len) }
+}
+#[no_mangle]
+extern "C" fn hk_high2023_comp_slice_4f64(pixels: *mut f64, len: usize) -> i32 {
+    unsafe { hk_high2023_comp_ffi::<_, 4>(pixels, len) }
+}
+
 #[no_mangle]
 extern "C" fn str2space_3f32(s: *const c_char, to: *const c_char) -> *const f32 {
     str2space_ffi::<f32, 3>(s, to)
@@ -1413,10 +5062,16 @@ macro_rules! cdef31 {
 // Transfer Functions
 cdef1!(srgb_eotf, srgb_eotf_f32, srgb_eotf_f64);
 cdef1!(srgb_oetf, srgb_oetf_f32, srgb_oetf_f64);
+cdef1!(srgb_eotf_fast, srgb_eotf_fast_f32, srgb_eotf_fast_f64);
+cdef1!(srgb_oetf_fast, srgb_oetf_fast_f32, srgb_oetf_fast_f64);
 cdef1!(pq_eotf, pq_eotf_f32, pq_eotf_f64);
 cdef1!(pqz_eotf, pqz_eotf_f32, pqz_eotf_f64);
 cdef1!(pq_oetf, pq_oetf_f32, pq_oetf_f64);
 cdef1!(pqz_oetf, pqz_oetf_f32, pqz_oetf_f64);
+cdef1!(hlg_oetf, hlg_oetf_f32, hlg_oetf_f64);
+cdef1!(hlg_eotf, hlg_eotf_f32, hlg_eotf_f64);
+cdef1!(bt709_oetf, bt709_oetf_f32, bt709_oetf_f64);
+cdef1!(bt709_eotf, bt709_eotf_f32, bt709_eotf_f64);
 
 // Helmholtz-Kohlrausch
 cdef31!(
@@ -1426,6 +5081,13 @@ cdef31!(
     hk_high2023_4f32,
     hk_high2023_4f64
 );
+cdef31!(
+    relative_luminance,
+    relative_luminance_3f32,
+    relative_luminance_3f64,
+    relative_luminance_4f32,
+    relative_luminance_4f64
+);
 cdef3!(
     hk_high2023_comp,
     hk_high2023_comp_3f32,
@@ -1442,6 +5104,13 @@ cdef3!(
     srgb_to_hsv_4f32,
     srgb_to_hsv_4f64
 );
+cdef3!(
+    srgb_to_hsv_deg,
+    srgb_to_hsv_deg_3f32,
+    srgb_to_hsv_deg_3f64,
+    srgb_to_hsv_deg_4f32,
+    srgb_to_hsv_deg_4f64
+);
 cdef3!(
     srgb_to_lrgb,
     srgb_to_lrgb_3f32,
@@ -1456,6 +5125,41 @@ cdef3!(
     lrgb_to_xyz_4f32,
     lrgb_to_xyz_4f64
 );
+cdef3!(
+    lrgb_to_xyz_precise,
+    lrgb_to_xyz_precise_3f32,
+    lrgb_to_xyz_precise_3f64,
+    lrgb_to_xyz_precise_4f32,
+    lrgb_to_xyz_precise_4f64
+);
+cdef3!(
+    lrgb_to_oklab,
+    lrgb_to_oklab_3f32,
+    lrgb_to_oklab_3f64,
+    lrgb_to_oklab_4f32,
+    lrgb_to_oklab_4f64
+);
+cdef3!(
+    lrgb_to_lp3,
+    lrgb_to_lp3_3f32,
+    lrgb_to_lp3_3f64,
+    lrgb_to_lp3_4f32,
+    lrgb_to_lp3_4f64
+);
+cdef3!(
+    lrgb_to_lrec2020,
+    lrgb_to_lrec2020_3f32,
+    lrgb_to_lrec2020_3f64,
+    lrgb_to_lrec2020_4f32,
+    lrgb_to_lrec2020_4f64
+);
+cdef3!(
+    lp3_to_lrec2020,
+    lp3_to_lrec2020_3f32,
+    lp3_to_lrec2020_3f64,
+    lp3_to_lrec2020_4f32,
+    lp3_to_lrec2020_4f64
+);
 cdef3!(
     xyz_to_cielab,
     xyz_to_cielab_3f32,
@@ -1470,6 +5174,13 @@ cdef3!(
     xyz_to_oklab_4f32,
     xyz_to_oklab_4f64
 );
+cdef3!(
+    oklab_to_oklrab,
+    oklab_to_oklrab_3f32,
+    oklab_to_oklrab_3f64,
+    oklab_to_oklrab_4f32,
+    oklab_to_oklrab_4f64
+);
 cdef3!(
     xyz_to_jzazbz,
     xyz_to_jzazbz_3f32,
@@ -1500,6 +5211,13 @@ cdef3!(
     hsv_to_srgb_4f32,
     hsv_to_srgb_4f64
 );
+cdef3!(
+    hsv_deg_to_srgb,
+    hsv_deg_to_srgb_3f32,
+    hsv_deg_to_srgb_3f64,
+    hsv_deg_to_srgb_4f32,
+    hsv_deg_to_srgb_4f64
+);
 cdef3!(
     lrgb_to_srgb,
     lrgb_to_srgb_3f32,
@@ -1514,6 +5232,13 @@ cdef3!(
     xyz_to_lrgb_4f32,
     xyz_to_lrgb_4f64
 );
+cdef3!(
+    xyz_to_lrgb_precise,
+    xyz_to_lrgb_precise_3f32,
+    xyz_to_lrgb_precise_3f64,
+    xyz_to_lrgb_precise_4f32,
+    xyz_to_lrgb_precise_4f64
+);
 cdef3!(
     cielab_to_xyz,
     cielab_to_xyz_3f32,
@@ -1528,6 +5253,41 @@ cdef3!(
     oklab_to_xyz_4f32,
     oklab_to_xyz_4f64
 );
+cdef3!(
+    oklrab_to_oklab,
+    oklrab_to_oklab_3f32,
+    oklrab_to_oklab_3f64,
+    oklrab_to_oklab_4f32,
+    oklrab_to_oklab_4f64
+);
+cdef3!(
+    oklab_to_lrgb,
+    oklab_to_lrgb_3f32,
+    oklab_to_lrgb_3f64,
+    oklab_to_lrgb_4f32,
+    oklab_to_lrgb_4f64
+);
+cdef3!(
+    lp3_to_lrgb,
+    lp3_to_lrgb_3f32,
+    lp3_to_lrgb_3f64,
+    lp3_to_lrgb_4f32,
+    lp3_to_lrgb_4f64
+);
+cdef3!(
+    lrec2020_to_lrgb,
+    lrec2020_to_lrgb_3f32,
+    lrec2020_to_lrgb_3f64,
+    lrec2020_to_lrgb_4f32,
+    lrec2020_to_lrgb_4f64
+);
+cdef3!(
+    lrec2020_to_lp3,
+    lrec2020_to_lp3_3f32,
+    lrec2020_to_lp3_3f64,
+    lrec2020_to_lp3_4f32,
+    lrec2020_to_lp3_4f64
+);
 cdef3!(
     jzazbz_to_xyz,
     jzazbz_to_xyz_3f32,
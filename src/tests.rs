@@ -199,6 +199,49 @@ fn irgb_convert() {
     assert_eq!(srgb_to_irgb(close_call), [254, 255, 255]);
 }
 
+#[test]
+fn banding() {
+    let smooth: Vec<[u8; 3]> = (0..=255).map(|n| [n, n, 255 - n]).collect();
+    assert_eq!(max_banding(&smooth), 1);
+
+    let banded: Vec<[u8; 3]> = (0..16).map(|n| srgb_to_irgb([n as f32 / 15.0, 0.5, 0.5])).collect();
+    assert_eq!(max_banding(&banded), 17);
+
+    assert_eq!(max_banding(&[[0u8, 0, 0, 0], [0, 0, 0, 200]]), 200);
+    assert_eq!(max_banding::<3>(&[]), 0);
+}
+
+#[test]
+fn irgb_linear() {
+    let linear = [0.2f32, 0.5, 1.0, 0.5];
+    let mut encoded = linear;
+    lrgb_to_srgb(&mut encoded);
+    let irgb = lrgb_to_irgb(linear);
+    assert_eq!(irgb, srgb_to_irgb(encoded));
+    assert_eq!(irgb, [124, 188, 255, 128]);
+    assert_ne!(irgb, srgb_to_irgb(linear));
+    let back: [f32; 4] = irgb_to_lrgb(irgb);
+    assert!(
+        back.iter().zip(linear.iter()).all(|(a, b)| (a - b).abs() < 5e-3),
+        "{:?}",
+        back
+    );
+    assert_eq!(back[3], 128.0 / 255.0);
+}
+
+#[test]
+fn irgb_clip() {
+    assert_eq!(
+        srgb_to_irgb_clip([-0.1, 0.5, 1.2]),
+        ([0, 128, 255], [true, false, true])
+    );
+    assert_eq!(
+        srgb_to_irgb_clip([0.0, 1.0, 1.0001, 0.35]),
+        ([0, 255, 255, 89], [false, false, true, false])
+    );
+    assert_eq!(srgb_to_irgb_clip([f32::NAN, 0.2, 0.35]).1, [true, false, false]);
+}
+
 #[test]
 fn hex_convert() {
     println!("IRGB_TO_HEX");
@@ -209,6 +252,28 @@ fn hex_convert() {
     assert_eq!(IRGB, hex_to_irgb(HEXA).unwrap());
 }
 
+#[test]
+fn hex_argb() {
+    assert_eq!(hex_to_irgb::<4>("#80FF0000"), Ok([0x80, 0xFF, 0x00, 0x00]));
+    assert_eq!(hex_to_irgb_argb("#80FF0000"), Ok([0xFF, 0x00, 0x00, 0x80]));
+    assert_eq!(hex_to_irgb_argb("FF0000"), Ok([0xFF, 0x00, 0x00, 0xFF]));
+    assert_eq!(hex_to_irgb_argb_e("#80FF000"), Err(HexError::BadLength(7)));
+    assert_eq!(irgb_to_hex_argb([0xFF, 0x00, 0x00, 0x80]), "#80FF0000");
+    assert_eq!(irgb_to_hex([0xFF, 0x00, 0x00, 0x80]), "#FF000080");
+    assert_eq!(hex_to_irgb_argb(&irgb_to_hex_argb(IRGBA)), Ok(IRGBA));
+}
+
+#[test]
+fn hex_slice() {
+    assert_eq!(
+        irgb_slice_to_hex(&[[255, 0, 0], [0, 128, 255], [16, 16, 16]], ", "),
+        "#FF0000, #0080FF, #101010"
+    );
+    assert_eq!(irgb_slice_to_hex(&[IRGBA, IRGBA], "\n"), format!("{}\n{}", HEXA, HEXA));
+    assert_eq!(irgb_slice_to_hex(&[IRGB], ","), HEX);
+    assert_eq!(irgb_slice_to_hex::<3>(&[], ","), "");
+}
+
 #[test]
 fn hex_validations() {
     for hex in [
@@ -232,6 +297,1190 @@ fn hex_validations() {
     }
 }
 
+#[test]
+fn hex_errors() {
+    assert_eq!(hex_to_irgb_e::<3>("#3359F2"), Ok(IRGB));
+    assert_eq!(hex_to_irgb_e::<3>(""), Err(HexError::Empty));
+    assert_eq!(hex_to_irgb_e::<3>("  #  "), Err(HexError::Empty));
+    assert_eq!(hex_to_irgb_e::<3>("#5F"), Err(HexError::BadLength(2)));
+    assert_eq!(hex_to_irgb_e::<4>("#ABCDEFF"), Err(HexError::BadLength(7)));
+    assert_eq!(hex_to_irgb_e::<3>("#ABCDEG"), Err(HexError::BadChar('G')));
+    assert_eq!(hex_to_irgb_e::<3>("##ABCDE"), Err(HexError::BadChar('#')));
+    assert_eq!(hex_to_irgb16_e::<3>("#ABCDEF"), Err(HexError::BadLength(6)));
+    assert_eq!(hex_to_irgb16_e::<4>("#ABCDEF01234x"), Err(HexError::BadChar('x')));
+
+    // String wrappers keep their messages
+    assert_eq!(hex_to_irgb::<3>("#5F"), Err(String::from("Incorrect hex length 2")));
+    assert_eq!(
+        hex_to_irgb::<3>("#ABCDEG"),
+        Err(String::from("Hex character 'G' out of bounds"))
+    );
+    assert_eq!(hex_to_irgb::<3>(""), Err(String::from("Incorrect hex length 0")));
+}
+
+#[test]
+fn hex16_convert() {
+    assert_eq!(hex_to_irgb16::<3>("#0123456789AB"), Ok([0x0123, 0x4567, 0x89AB]));
+    assert_eq!(
+        hex_to_irgb16::<4>("#0123456789AB"),
+        Ok([0x0123, 0x4567, 0x89AB, 0xFFFF])
+    );
+    assert_eq!(
+        hex_to_irgb16::<4>("#0123456789abcdef"),
+        Ok([0x0123, 0x4567, 0x89AB, 0xCDEF])
+    );
+    assert_eq!(hex_to_irgb16::<3>("#0123456789abcdef"), Ok([0x0123, 0x4567, 0x89AB]));
+    assert_eq!(hex_to_irgb16_default::<4, 0>("FFFF00000000"), Ok([0xFFFF, 0, 0, 0]));
+}
+
+#[test]
+fn hex16_validations() {
+    for hex in [
+        "#ABCDEF012345",
+        "#abcdef012345",
+        "#ABCDEF0123456789",
+        "#abcdef0123456789",
+        "ABCDEF012345",
+        "  ABCDEF012345     ",
+        "  #ABCDEF012345     ",
+    ] {
+        assert!(hex_to_irgb16::<3>(hex).is_ok(), "NOT VALID 3: '{}'", hex);
+        assert!(hex_to_irgb16::<4>(hex).is_ok(), "NOT VALID 4: '{}'", hex);
+    }
+    for hex in [
+        "",
+        "#",
+        "#ABCDEF",
+        "#ABCDEF01",
+        "#ABCDEF01234G",
+        "#abcdef01234g",
+        "#ABCDEF0123456",
+        "##ABCDEF012345",
+        "ABCDEF012345#",
+        "#ABCDEF01234567890",
+    ] {
+        assert!(hex_to_irgb16::<3>(hex).is_err(), "NOT INVALID 3: '{}'", hex);
+        assert!(hex_to_irgb16::<4>(hex).is_err(), "NOT INVALID 4: '{}'", hex);
+    }
+}
+
+#[test]
+fn display_rgb8() {
+    // vivid cyan well outside of sRGB
+    let oklch = [0.7, 0.3, 200.0f64];
+    let clipped = to_display_rgb8(oklch, Space::OKLCH, false);
+    let mapped = to_display_rgb8(oklch, Space::OKLCH, true);
+    assert_ne!(clipped, mapped);
+
+    let mut reference = oklch;
+    convert_space(Space::OKLCH, Space::SRGB, &mut reference);
+    assert_eq!(clipped, srgb_to_irgb(reference.map(|c| c.clamp(0.0, 1.0) as f32)));
+    gamut_map_srgb(&mut reference);
+    assert_eq!(mapped, srgb_to_irgb(reference.map(|c| c as f32)));
+
+    // mapping keeps lightness closer than clipping
+    let to_oklch = |rgb: [u8; 3]| {
+        let mut p = irgb_to_srgb::<f64, 3>(rgb);
+        convert_space(Space::SRGB, Space::OKLCH, &mut p);
+        p
+    };
+    let (c, m) = (to_oklch(clipped), to_oklch(mapped));
+    assert!((m[0] - oklch[0]).abs() < (c[0] - oklch[0]).abs(), "{:?} {:?}", c, m);
+
+    // in gamut colors are simply quantized either way
+    assert_eq!(
+        to_display_rgb8([0.2, 0.5, 0.6f32, f32::NAN], Space::SRGB, true),
+        [51, 128, 153, 255]
+    );
+    assert_eq!(
+        to_display_rgb8([0.2, 0.5, 0.6f32, 0.5], Space::SRGB, false),
+        [51, 128, 153, 128]
+    );
+}
+
+#[test]
+fn int_depths() {
+    assert_eq!(srgb_to_int([1.0, 0.0, 0.5], 10), [1023, 0, 512]);
+    assert_eq!(srgb_to_int([1.5, -0.5, 1.0, 1.0], 12), [4095, 0, 4095, 4095]);
+    assert_eq!(srgb_to_int([1.0f32; 3], 32), [u32::MAX; 3]);
+    for bits in [1, 8, 10, 12, 16] {
+        let max = (1u32 << bits) - 1;
+        for n in [0, 1, max / 3, max / 2, max - 1, max] {
+            let pixel = [n, max - n, n / 2];
+            assert_eq!(
+                srgb_to_int(int_to_srgb::<f32, 3>(pixel, bits), bits),
+                pixel,
+                "{} bits",
+                bits
+            );
+        }
+    }
+    // 8 bit matches the existing helpers
+    for n in 0..=255u8 {
+        assert_eq!(srgb_to_int([n as f32 / 255.0; 3], 8), [n as u32; 3]);
+        assert_eq!(int_to_srgb::<f32, 3>([n as u32; 3], 8), irgb_to_srgb::<f32, 3>([n; 3]));
+    }
+}
+
+#[test]
+#[should_panic(expected = "bit depth must be 1 to 32")]
+fn int_depth_zero() {
+    srgb_to_int([0.5f32; 3], 0);
+}
+
+#[test]
+fn hsv_degrees() {
+    for (srgb, hsv) in SRGB.iter().zip(HSV.iter()) {
+        let mut deg = *srgb;
+        srgb_to_hsv_deg(&mut deg);
+        let mut norm = *srgb;
+        srgb_to_hsv(&mut norm);
+        assert_eq!(deg, [norm[0] * 360.0, norm[1], norm[2]]);
+        assert!((deg[0] - hsv[0] * 360.0).abs() < 1e-3 * 360.0, "{:?} {:?}", deg, hsv);
+
+        hsv_deg_to_srgb(&mut deg);
+        pix_cmp(&[deg], &[*srgb], 1e-3, &[]);
+    }
+}
+
+#[test]
+fn hsv_out_of_range() {
+    // HDR value and negative saturation rows of the reference
+    for n in [8, 9] {
+        let mut srgb = HSV[n];
+        hsv_to_srgb(&mut srgb);
+        pix_cmp(&[srgb], &[SRGB[n]], 1e-6, &[]);
+    }
+    let mut hdr = [0.0f64, 0.5, 4.0];
+    hsv_to_srgb(&mut hdr);
+    assert_eq!(hdr, [4.0, 2.0, 2.0]);
+    let mut oversaturated = [0.0f64, 1.5, 1.0];
+    hsv_to_srgb(&mut oversaturated);
+    assert_eq!(oversaturated, [1.0, -0.5, -0.5]);
+    srgb_to_hsv(&mut oversaturated);
+    pix_cmp(&[oversaturated], &[[0.0, 1.5, 1.0]], 1e-9, &[]);
+    // hue wraps
+    for h in [1.25, -0.75, 3.25] {
+        let mut wrapped = [h, 0.8, 0.6];
+        hsv_to_srgb(&mut wrapped);
+        let mut reference = [0.25, 0.8, 0.6];
+        hsv_to_srgb(&mut reference);
+        pix_cmp(&[wrapped], &[reference], 1e-9, &[]);
+    }
+}
+
+#[test]
+fn hsv_keep_hue() {
+    let mut hue = 0.0f32;
+    for s in (0..=4).rev() {
+        let mut pixel = [0.5, 0.5, 0.5 + s as f32 / 8.0, 0.25];
+        srgb_to_hsv_keep_hue(&mut pixel, hue);
+        assert_eq!(pixel[0], 2.0 / 3.0, "{:?}", pixel);
+        assert_eq!(pixel[3], 0.25);
+        hue = pixel[0];
+    }
+    let mut gray = [0.5f32, 0.5, 0.5];
+    srgb_to_hsv(&mut gray);
+    assert_eq!(gray[0], 0.0);
+    let mut red = [1.0f32, 0.0, 0.0];
+    srgb_to_hsv_keep_hue(&mut red, 0.5);
+    assert_eq!(red[0], 0.0);
+}
+
+#[test]
+fn hue_chroma_core() {
+    let colors: Vec<[f64; 3]> = SRGB
+        .iter()
+        .copied()
+        .chain([
+            [0.2, 0.4, 0.6],
+            [1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1e-12],
+            [0.5; 3],
+        ])
+        .collect();
+    for srgb in colors {
+        let [mut hsv, mut hsl, mut hwb] = [srgb; 3];
+        srgb_to_hsv(&mut hsv);
+        srgb_to_hsl(&mut hsl);
+        srgb_to_hwb(&mut hwb);
+        assert_eq!(hsv[0].to_bits(), hsl[0].to_bits(), "{:?}", srgb);
+        assert_eq!(hsv[0].to_bits(), hwb[0].to_bits(), "{:?}", srgb);
+        assert!((0.0..1.0).contains(&hsv[0]), "{:?}", srgb);
+
+        hsl_to_srgb(&mut hsl);
+        hwb_to_srgb(&mut hwb);
+        // hsl and hwb are only defined for 0.0..=1.0, and go gray outside of it
+        if srgb.iter().all(|c| (0.0..=1.0).contains(c)) {
+            pix_cmp(&[hsl], &[srgb], 1e-12, &[]);
+            pix_cmp(&[hwb], &[srgb], 1e-12, &[]);
+        }
+    }
+
+    let mut hsl = [0.2, 0.4, 0.6];
+    srgb_to_hsl(&mut hsl);
+    pix_cmp(&[hsl], &[[210.0 / 360.0, 0.5, 0.4]], 1e-12, &[]);
+    let mut hwb = [0.2, 0.4, 0.6];
+    srgb_to_hwb(&mut hwb);
+    pix_cmp(&[hwb], &[[210.0 / 360.0, 0.2, 0.4]], 1e-12, &[]);
+
+    // css: whiteness and blackness past 100% normalize to gray
+    let mut gray = [0.3, 0.6, 0.9];
+    hwb_to_srgb(&mut gray);
+    pix_cmp(&[gray], &[[0.4; 3]], 1e-12, &[]);
+}
+
+#[test]
+fn delta_e() {
+    // Sharma, Wu, Dalal 2005 CIEDE2000 test data
+    let pairs: &[([f64; 3], [f64; 3], f64)] = &[
+        ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+        ([50.0, 3.1571, -77.2803], [50.0, 0.0, -82.7485], 2.8615),
+        ([50.0, 2.8361, -74.0200], [50.0, 0.0, -82.7485], 3.4412),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+        ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+        ([50.0, 2.5, 0.0], [61.0, -5.0, 29.0], 22.8977),
+        ([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+    ];
+    let a: Vec<[f64; 3]> = pairs.iter().map(|p| p.0).collect();
+    let b: Vec<[f64; 3]> = pairs.iter().map(|p| p.1).collect();
+    let mut out = vec![0.0; pairs.len()];
+    delta_e_2000_slice(&a, &b, &mut out);
+    for ((lab1, lab2, reference), de) in pairs.iter().zip(out.iter()) {
+        assert!(
+            (de - reference).abs() < 1e-4,
+            "{:?} {:?} {} != {}",
+            lab1,
+            lab2,
+            de,
+            reference
+        );
+        assert!(
+            (delta_e_2000(lab2, lab1) - reference).abs() < 1e-4,
+            "Asymmetric {:?} {:?}",
+            lab1,
+            lab2
+        );
+        let de32 = delta_e_2000(&lab1.map(|c| c as f32), &lab2.map(|c| c as f32));
+        assert!((de32 as f64 - reference).abs() < 1e-3, "F32 {:?} {:?}", lab1, lab2);
+    }
+
+    let a = [[50.0f32, 0.0, 0.0], [0.5, 0.1, -0.1]];
+    let b = [[53.0f32, 4.0, 0.0], [0.5, 0.0, 0.0]];
+    let mut out = [0.0; 2];
+    delta_e_76_slice(&a, &b, &mut out);
+    assert_eq!(out[0], 5.0);
+    delta_e_ok_slice(&a[1..], &b[1..], &mut out[1..]);
+    assert!((out[1] - 0.02f32.sqrt()).abs() < 1e-6);
+    assert_eq!(delta_e_2000(&a[0], &a[0]), 0.0);
+}
+
+#[test]
+#[should_panic]
+#[cfg(debug_assertions)]
+fn delta_e_slice_len() {
+    delta_e_2000_slice(&[[0.0f32; 3]; 2], &[[0.0; 3]; 3], &mut [0.0; 2]);
+}
+
+#[test]
+fn delta_e_cmc_ratios() {
+    // colour-science delta_E_CMC example
+    let (a, b) = (
+        [100.0, 21.57210357, 272.22819350],
+        [100.0, 426.67945353, 72.39590835f64],
+    );
+    assert!((delta_e_cmc(&a, &b, 2.0, 1.0) - 172.7047712).abs() < 1e-6);
+    assert!((delta_e_cmc(&a, &b, 1.0, 1.0) - 172.7047712).abs() < 1e-6);
+
+    // (reference, sample, 2:1, 1:1)
+    let pairs: &[([f64; 3], [f64; 3], f64, f64)] = &[
+        ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 37.923276, 42.108755),
+        (
+            [60.2574, -34.0099, 36.2677],
+            [60.4626, -34.1751, 39.4387],
+            1.420486,
+            1.428230,
+        ),
+        ([10.0, 5.0, -3.0], [12.0, 4.0, -2.0], 2.444286, 4.178931),
+        ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 3.504809, 3.504809),
+    ];
+    for (lab1, lab2, cmc21, cmc11) in pairs {
+        let de21 = delta_e_cmc(lab1, lab2, 2.0, 1.0);
+        let de11 = delta_e_cmc(lab1, lab2, 1.0, 1.0);
+        assert!(
+            (de21 - cmc21).abs() < 1e-5,
+            "{:?} {:?} {} != {}",
+            lab1,
+            lab2,
+            de21,
+            cmc21
+        );
+        assert!(
+            (de11 - cmc11).abs() < 1e-5,
+            "{:?} {:?} {} != {}",
+            lab1,
+            lab2,
+            de11,
+            cmc11
+        );
+        let de32 = delta_e_cmc(&lab1.map(|c| c as f32), &lab2.map(|c| c as f32), 2.0, 1.0);
+        assert!((de32 as f64 - cmc21).abs() < 1e-3);
+    }
+    assert_eq!(
+        delta_e_cmc(&[40.0, 20.0, -10.0f32], &[40.0, 20.0, -10.0], 2.0, 1.0),
+        0.0
+    );
+}
+
+#[test]
+fn luminance() {
+    assert_eq!(relative_luminance(&[0.0f32, 0.0, 0.0]), 0.0);
+    assert!((relative_luminance(&[1.0f64, 1.0, 1.0]) - 1.0).abs() < 1e-6);
+    assert!((relative_luminance(&[1.0f32, 0.0, 0.0, 0.5]) - 0.2126).abs() < 1e-6);
+    for (srgb, xyz) in SRGB.iter().zip(XYZ.iter()) {
+        assert!((relative_luminance(srgb) - xyz[1]).abs() < 1e-4, "{:?} {:?}", srgb, xyz);
+    }
+}
+
+#[test]
+fn contrast() {
+    assert!((contrast_ratio(&[0.0f64; 3], &[1.0; 3]) - 21.0).abs() < 1e-4);
+    assert_eq!(
+        contrast_ratio(&[1.0f32; 3], &[0.0; 3]),
+        contrast_ratio(&[0.0f32; 3], &[1.0; 3])
+    );
+    assert_eq!(contrast_ratio(&[0.3f32, 0.6, 0.2], &[0.3, 0.6, 0.2]), 1.0);
+
+    // reachable by darkening
+    let bg = [1.0f64, 1.0, 1.0];
+    let mut fg = [0.6, 0.7, 0.9];
+    adjust_to_contrast(&mut fg, &bg, 4.5);
+    let ratio = contrast_ratio(&fg, &bg);
+    assert!((4.5..4.52).contains(&ratio), "{} {:?}", ratio, fg);
+    assert!(fg[2] > fg[0], "{:?}", fg);
+
+    // reachable by lightening
+    let bg = [0.1f64, 0.1, 0.15];
+    let mut fg = [0.3, 0.2, 0.2];
+    adjust_to_contrast(&mut fg, &bg, 7.0);
+    let ratio = contrast_ratio(&fg, &bg);
+    assert!((7.0..7.05).contains(&ratio), "{} {:?}", ratio, fg);
+
+    // already fine
+    let mut fg = [0.0f32; 3];
+    adjust_to_contrast(&mut fg, &[1.0; 3], 4.5);
+    assert_eq!(fg, [0.0; 3]);
+
+    // unreachable on a mid gray, clamps to the better extreme
+    let bg = [0.5f64, 0.5, 0.5];
+    let mut fg = [0.45, 0.5, 0.55];
+    adjust_to_contrast(&mut fg, &bg, 10.0);
+    assert!(
+        fg.iter().all(|c| (c - 1.0).abs() < 1e-6) || fg.iter().all(|c| c.abs() < 1e-6),
+        "{:?}",
+        fg
+    );
+    assert!(contrast_ratio(&fg, &bg) < 10.0);
+    let mut fg = [0.5, 0.5, 0.55];
+    adjust_to_contrast(&mut fg, &bg, 30.0);
+    let best = contrast_ratio(&[0.0; 3], &bg).max(contrast_ratio(&[1.0; 3], &bg));
+    assert!((contrast_ratio(&fg, &bg) - best).abs() < 1e-6, "{:?}", fg);
+}
+
+#[test]
+fn lms_matrices() {
+    for matrix in [
+        LmsMatrix::HPE,
+        LmsMatrix::Bradford,
+        LmsMatrix::CAT02,
+        LmsMatrix::CAT16,
+        LmsMatrix::StockmanSharpe,
+    ] {
+        let (m, i) = (matrix.m(), inv(matrix.m()));
+        assert!(det(m).abs() > 1e-3, "{:?} singular", matrix);
+        for (row, m_row) in m.iter().enumerate() {
+            for col in 0..3 {
+                let dot: f32 = m_row.iter().zip(i.iter()).map(|(a, i_row)| a * i_row[col]).sum();
+                let identity = if row == col { 1.0 } else { 0.0 };
+                assert!(
+                    (dot - identity).abs() < 1e-6,
+                    "{:?} M * M^-1 = {} @ {} {}",
+                    matrix,
+                    dot,
+                    row,
+                    col
+                );
+            }
+        }
+
+        let mut owned = XYZ.to_vec();
+        owned.iter_mut().for_each(|p| {
+            xyz_to_lms(p, matrix);
+            lms_to_xyz(p, matrix);
+        });
+        pix_cmp(&owned, XYZ, 1e-3, &[]);
+    }
+    assert_eq!(LmsMatrix::default(), LmsMatrix::HPE);
+
+    // Bradford white should land on the published D65 cone response
+    let mut white = D65.map(|c| c as f64);
+    xyz_to_lms(&mut white, LmsMatrix::Bradford);
+    pix_cmp(&[white], &[[0.94142, 1.04040, 1.08970]], 1e-3, &[]);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn grain() {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+    for base in [[0.5f64; 3], [0.1, 0.3, 0.6], [0.05; 3]] {
+        let mut reference = base;
+        convert_space(Space::SRGB, Space::OKLAB, &mut reference);
+        let (mut sum, mut spread) = (0.0, 0.0f64);
+        const SAMPLES: usize = 20000;
+        for _ in 0..SAMPLES {
+            let mut pixel = base;
+            add_grain(&mut pixel, 0.05, &mut rng);
+            convert_space(Space::SRGB, Space::OKLAB, &mut pixel);
+            assert!((pixel[0] - reference[0]).abs() <= 0.05 + 1e-6, "{:?}", pixel);
+            pix_cmp(
+                &[[pixel[1], pixel[2], 0.0]],
+                &[[reference[1], reference[2], 0.0]],
+                1e-4,
+                &[],
+            );
+            sum += pixel[0];
+            spread = spread.max((pixel[0] - reference[0]).abs());
+        }
+        assert!((sum / SAMPLES as f64 - reference[0]).abs() < 1e-3, "{:?}", base);
+        assert!(spread > 0.04, "{}", spread);
+    }
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn random_colors() {
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+    for space in Space::ALL {
+        let mut hues = [false; 4];
+        for _ in 0..1000 {
+            let color: [f64; 3] = random_color(*space, &mut rng);
+            if Space::UCS_POLAR.contains(space) {
+                assert!((0.0..=360.0).contains(&color[2]), "{:?} {:?}", space, color);
+                hues[(color[2] / 90.0) as usize % 4] = true;
+            }
+            let mut srgb = color;
+            convert_space(*space, Space::SRGB, &mut srgb);
+            assert!(
+                srgb.iter().all(|c| (-1e-6..=1.0 + 1e-6).contains(c)),
+                "{:?} {:?} -> {:?}",
+                space,
+                color,
+                srgb
+            );
+        }
+        if Space::UCS_POLAR.contains(space) {
+            assert_eq!(hues, [true; 4], "{:?} hue not uniform", space);
+        }
+    }
+}
+
+#[test]
+fn hue_histograms() {
+    let mut image = vec![[0.9f32, 0.1, 0.1]; 30];
+    image.extend_from_slice(&[[0.1, 0.2, 0.9]; 30]);
+    image.extend_from_slice(&[[0.5, 0.5, 0.5]; 40]);
+    for space in [Space::HSV, Space::CIELCH, Space::OKLCH, Space::JZCZHZ] {
+        let histogram = hue_histogram(&image, 12, space).unwrap();
+        assert_eq!(histogram.len(), 12);
+        assert!((histogram.iter().sum::<f32>() - 1.0).abs() < 1e-5, "{:?}", histogram);
+        let peaks: Vec<usize> = (0..12).filter(|n| histogram[*n] > 0.05).collect();
+        assert_eq!(peaks.len(), 2, "{} {:?}", space, histogram);
+        assert!(
+            histogram[peaks[0]] + histogram[peaks[1]] > 0.99,
+            "{} {:?}",
+            space,
+            histogram
+        );
+    }
+    assert_eq!(hue_histogram(&[[0.5f64; 3]], 4, Space::HSV), Some(vec![0.0; 4]));
+    assert_eq!(hue_histogram(&image, 0, Space::OKLCH), Some(vec![]));
+    assert_eq!(hue_histogram(&image, 12, Space::OKLAB), None);
+    assert_eq!(hue_histogram(&image, 12, Space::SRGB), None);
+}
+
+#[test]
+fn colorfulnesses() {
+    let gray: Vec<[f32; 3]> = (0..=10).map(|n| [n as f32 / 10.0; 3]).collect();
+    assert!(colorfulness(&gray) < 1e-3, "{}", colorfulness(&gray));
+    let rainbow: Vec<[f32; 3]> = (0..36)
+        .map(|n| {
+            let mut pixel = [n as f32 / 36.0, 1.0, 1.0];
+            hsv_to_srgb(&mut pixel);
+            pixel
+        })
+        .collect();
+    assert!(colorfulness(&rainbow) > 0.2, "{}", colorfulness(&rainbow));
+    let muted: Vec<[f32; 3]> = rainbow.iter().map(|p| p.map(|c| c * 0.3 + 0.35)).collect();
+    assert!(colorfulness(&muted) < colorfulness(&rainbow));
+    assert_eq!(colorfulness::<f64>(&[]), 0.0);
+}
+
+#[test]
+fn gamut_map() {
+    // in gamut untouched
+    for pixel in [[0.2f64, 0.35, 0.95], [0.0, 0.0, 0.0], [1.0, 1.0, 1.0]] {
+        let mut mapped = pixel;
+        gamut_map_srgb(&mut mapped);
+        assert_eq!(mapped, pixel);
+    }
+
+    for lch in [[0.7f64, 0.4, 150.0], [0.3, 0.3, 270.0], [0.9, 0.2, 30.0]] {
+        let mut pixel = [lch[0], lch[1], lch[2], 0.25];
+        convert_space(Space::OKLCH, Space::SRGB, &mut pixel);
+        gamut_map_srgb(&mut pixel);
+        assert!(pixel.iter().take(3).all(|c| (0.0..=1.0).contains(c)), "{:?}", pixel);
+        assert_eq!(pixel[3], 0.25);
+        convert_space(Space::SRGB, Space::OKLCH, &mut pixel);
+        assert!((pixel[0] - lch[0]).abs() < 0.05, "{:?} {:?}", lch, pixel);
+        assert!(pixel[1] < lch[1], "{:?} {:?}", lch, pixel);
+        assert!((pixel[2] - lch[2]).abs() < 5.0, "{:?} {:?}", lch, pixel);
+    }
+
+    let mut bright = [2.0f32, 2.0, 1.5];
+    gamut_map_srgb(&mut bright);
+    assert_eq!(bright, [1.0; 3]);
+
+    let mut broken = [f32::NAN, f32::INFINITY, 0.5, f32::NAN];
+    gamut_map_srgb(&mut broken);
+    assert_eq!(broken[..3], [0.0, 1.0, 0.5]);
+}
+
+#[test]
+fn skin_protect() {
+    let chroma = |srgb: [f64; 3]| {
+        let mut lch = srgb;
+        convert_space(Space::SRGB, Space::OKLCH, &mut lch);
+        lch[1]
+    };
+    // OKLCH hue ~35
+    let skin = [0.8, 0.55, 0.5];
+    // OKLCH hue ~260
+    let blue = [0.4, 0.5, 0.7];
+    let (mut skin_sat, mut blue_sat) = (skin, blue);
+    saturate_protect_skin(&mut skin_sat, 0.5);
+    saturate_protect_skin(&mut blue_sat, 0.5);
+    let skin_gain = chroma(skin_sat) / chroma(skin);
+    let blue_gain = chroma(blue_sat) / chroma(blue);
+    assert!(skin_gain < 1.1, "{}", skin_gain);
+    assert!((blue_gain - 1.5).abs() < 1e-3, "{}", blue_gain);
+
+    let mut gray = [0.5; 3];
+    saturate_protect_skin(&mut gray, 1.0);
+    assert!(chroma(gray) <= chroma([0.5; 3]) * 2.0 + 1e-9, "{}", chroma(gray));
+    let mut desat = blue;
+    saturate_protect_skin(&mut desat, -1.0);
+    assert!(chroma(desat) < 1e-4, "{}", chroma(desat));
+    assert!(desat.iter().all(|c| (0.0..=1.0).contains(c)));
+}
+
+#[test]
+fn exposure() {
+    let linear = |srgb: [f64; 3]| {
+        let mut p = srgb;
+        srgb_to_lrgb(&mut p);
+        p
+    };
+    let gray = [0.5f64; 3];
+    let mut brighter = gray;
+    adjust_exposure(&mut brighter, 1.0, false);
+    pix_cmp(&[linear(brighter)], &[linear(gray).map(|c| c * 2.0)], 1e-9, &[]);
+    let mut darker = gray;
+    adjust_exposure(&mut darker, -2.0, false);
+    pix_cmp(&[linear(darker)], &[linear(gray).map(|c| c / 4.0)], 1e-9, &[]);
+
+    let mut hdr = [0.9f32, 0.5, 0.1];
+    adjust_exposure(&mut hdr, 2.0, false);
+    assert!(hdr[0] > 1.0, "{:?}", hdr);
+    let mut sdr = [0.9f32, 0.5, 0.1];
+    adjust_exposure(&mut sdr, 2.0, true);
+    assert_eq!(sdr[0], 1.0);
+    assert!(sdr.iter().all(|c| (0.0..=1.0).contains(c)), "{:?}", sdr);
+}
+
+#[test]
+fn max_lightness() {
+    for (c, h) in [(0.1, 30.0), (0.15, 140.0), (0.05, 260.0), (0.2, 330.0), (0.12, 90.0)] {
+        let l = max_lightness_srgb(c, h);
+        assert!(l > 0.0 && l < 1.0, "{} {} {}", c, h, l);
+        let mut srgb = [l, c, h];
+        convert_space(Space::OKLCH, Space::SRGB, &mut srgb);
+        // sits on the boundary: in gamut with a channel pinned at 0 or 1
+        assert!(srgb.iter().all(|v| (-1e-5..=1.0 + 1e-5).contains(v)), "{:?}", srgb);
+        assert!(
+            srgb.iter().any(|v| v.abs() < 1e-4 || (v - 1.0).abs() < 1e-4),
+            "{:?}",
+            srgb
+        );
+        // and slightly lighter leaves the gamut
+        let mut lighter = [l + 1e-3, c, h];
+        convert_space(Space::OKLCH, Space::SRGB, &mut lighter);
+        assert!(lighter.iter().any(|v| *v > 1.0 || *v < 0.0), "{:?}", lighter);
+    }
+    assert!((max_lightness_srgb(0.0f64, 0.0) - 1.0).abs() < 1e-3);
+    assert!((max_lightness_srgb(0.0f32, 0.0) - 1.0).abs() < 1e-3);
+    assert!(max_lightness_srgb(0.5f64, 200.0).is_nan());
+
+    // just under the cusp only a sliver of lightness fits, narrower than the scan steps
+    let mut red = [1.0f64, 0.0, 0.0];
+    convert_space(Space::SRGB, Space::OKLCH, &mut red);
+    for scale in [0.999, 0.9999] {
+        let l = max_lightness_srgb(red[1] * scale, red[2]);
+        assert!((l - red[0]).abs() < 1e-2, "{} {:?}", l, red);
+        let l32 = max_lightness_srgb((red[1] * scale) as f32, red[2] as f32);
+        assert!((l32 as f64 - red[0]).abs() < 1e-2, "{} {:?}", l32, red);
+    }
+    assert!(max_lightness_srgb(red[1] * 1.001, red[2]).is_nan());
+}
+
+#[test]
+fn invert_lightness_dark_mode() {
+    let dark_blue = [0.05, 0.1, 0.4f64];
+    let before = to_oklch(dark_blue);
+
+    let mut light = dark_blue;
+    invert_lightness(&mut light);
+    let after = to_oklch(light);
+    assert!(after[0] > 0.6, "{:?}", after);
+    assert!((after[2] - before[2]).abs() < 5.0, "{:?} {:?}", before, after);
+    assert!(light[2] > light[0] && light[2] > light[1], "{:?}", light);
+
+    // naive rgb invert lands on the opposite hue
+    let naive = to_oklch(dark_blue.map(|c| 1.0 - c));
+    assert!((naive[2] - before[2]).abs() > 90.0, "{:?}", naive);
+
+    let mut white = [1.0f64; 3];
+    invert_lightness(&mut white);
+    assert!(white.iter().all(|c| c.abs() < 1e-3), "{:?}", white);
+
+    let mut twice = [0.3, 0.45, 0.5f64];
+    invert_lightness(&mut twice);
+    invert_lightness(&mut twice);
+    pix_cmp(&[twice], &[[0.3, 0.45, 0.5]], 1e-3, &[]);
+}
+
+#[test]
+fn clip_hue() {
+    let hue = |srgb: [f64; 3]| {
+        let mut lch = srgb;
+        convert_space(Space::SRGB, Space::OKLCH, &mut lch);
+        lch[2]
+    };
+    let drift = |a: f64, b: f64| ((a - b + 180.0).rem_euclid(360.0) - 180.0).abs();
+    for lch in [
+        [0.7, 0.35, 140.0],
+        [0.5, 0.4, 300.0],
+        [0.9, 0.3, 60.0],
+        [0.4, 0.3, 20.0],
+    ] {
+        let mut original = lch;
+        convert_space(Space::OKLCH, Space::SRGB, &mut original);
+        let naive = original.map(|c| c.clamp(0.0, 1.0));
+        let mut preserved = original;
+        clip_preserve_hue(&mut preserved);
+        assert!(preserved.iter().all(|c| (0.0..=1.0).contains(c)), "{:?}", preserved);
+        let (before, after) = (drift(hue(naive), lch[2]), drift(hue(preserved), lch[2]));
+        assert!(after <= before && after < 1.5, "{:?} {} -> {}", lch, before, after);
+    }
+
+    let mut inside = [0.2, 0.5, 0.8];
+    clip_preserve_hue(&mut inside);
+    assert_eq!(inside, [0.2, 0.5, 0.8]);
+    let mut white = [1.2f32, 1.1, 1.3];
+    clip_preserve_hue(&mut white);
+    assert_eq!(white, [1.0; 3]);
+}
+
+#[test]
+fn lab_values() {
+    let mut gray = [50.0f64, 0.0, 0.0];
+    convert_space(Space::CIELAB, Space::SRGB, &mut gray);
+    assert!((lab_value(&gray) - 5.0).abs() < 1e-6, "{}", lab_value(&gray));
+    assert!(lab_value(&[0.0f32; 3]).abs() < 1e-6);
+    assert!((lab_value(&[1.0f32; 3]) - 10.0).abs() < 1e-4);
+
+    let mut color = [0.6, 0.3, 0.2];
+    let mut before = color;
+    convert_space(Space::SRGB, Space::CIELAB, &mut before);
+    set_lab_value(&mut color, 7.0);
+    assert!((lab_value(&color) - 7.0).abs() < 1e-6);
+    let mut after = color;
+    convert_space(Space::SRGB, Space::CIELAB, &mut after);
+    pix_cmp(&[[after[1], after[2], 0.0]], &[[before[1], before[2], 0.0]], 1e-4, &[]);
+}
+
+#[test]
+fn posterizing() {
+    for n in 0..=255u8 {
+        let original: [f32; 3] = irgb_to_srgb([n, 255 - n, n / 2]);
+        let mut pixel = original;
+        posterize(&mut pixel, 256, Space::SRGB);
+        assert_eq!(srgb_to_irgb(pixel), [n, 255 - n, n / 2]);
+    }
+
+    let mut lightness = Vec::new();
+    for n in 0..=16 {
+        let mut p = [n as f64 / 16.0, n as f64 / 16.0, 0.5 + n as f64 / 40.0];
+        posterize(&mut p, 2, Space::OKLAB);
+        convert_space(Space::SRGB, Space::OKLCH, &mut p);
+        let l = (p[0] * 1e3).round() as i64;
+        if !lightness.contains(&l) {
+            lightness.push(l)
+        }
+    }
+    assert_eq!(lightness.len(), 2, "{:?}", lightness);
+
+    // bands land on even OKLAB L steps and grays stay neutral
+    let mut gray = [0.5f64; 3];
+    posterize(&mut gray, 5, Space::OKLAB);
+    let mut lch = gray;
+    convert_space(Space::SRGB, Space::OKLCH, &mut lch);
+    assert!((lch[0] - 0.5).abs() < 1e-3, "{:?}", lch);
+    assert!(lch[1] < 1e-3, "{:?}", lch);
+}
+
+#[test]
+fn gamut_ranges() {
+    let quants = Space::OKLAB.srgb_quants();
+    let mut pixel = [0.5f32, 5.0, -5.0, 0.25];
+    clamp_to_gamut_ranges(&mut pixel, Space::OKLAB);
+    assert_eq!(pixel, [0.5, quants[100][1], quants[0][2], 0.25]);
+    assert!(pixel[1] < 0.3 && pixel[2] > -0.4, "{:?}", pixel);
+
+    // hue untouched
+    let mut pixel = [2.0f64, 3.0, 720.0];
+    clamp_to_gamut_ranges(&mut pixel, Space::OKLCH);
+    assert_eq!(pixel[2], 720.0);
+    let quants = Space::OKLCH.srgb_quants();
+    assert_eq!(pixel, [quants[100][0] as f64, quants[100][1] as f64, 720.0]);
+
+    // in range values unchanged
+    let mut pixel = [0.2f32, 0.5, 0.9];
+    clamp_to_gamut_ranges(&mut pixel, Space::SRGB);
+    assert_eq!(pixel, [0.2, 0.5, 0.9]);
+}
+
+#[test]
+fn soft_proofing() {
+    // primaries pulled a fifth of the way towards white, keeping white
+    let narrow = [
+        [7.0 / 6.0, -1.0 / 12.0, -1.0 / 12.0],
+        [-1.0 / 12.0, 7.0 / 6.0, -1.0 / 12.0],
+        [-1.0 / 12.0, -1.0 / 12.0, 7.0 / 6.0],
+    ];
+    for pixel in [[0.5f64, 0.5, 0.5], [0.5, 0.45, 0.4], [1.0, 1.0, 1.0]] {
+        let mut proofed = pixel;
+        soft_proof(&mut proofed, narrow);
+        assert_eq!(proofed, pixel);
+    }
+
+    for pixel in [[1.0f64, 0.0, 0.0], [0.1, 0.8, 0.3], [0.2, 0.3, 0.9]] {
+        let mut proofed = pixel;
+        soft_proof(&mut proofed, narrow);
+        let [mut before, mut after] = [pixel, proofed];
+        convert_space(Space::SRGB, Space::CIELCH, &mut before);
+        convert_space(Space::SRGB, Space::CIELCH, &mut after);
+        // several times a just noticeable difference
+        assert!(after[1] < before[1] - 5.0, "{:?} {:?}", before, after);
+        assert!((after[0] - before[0]).abs() < 1e-4, "{:?} {:?}", before, after);
+        assert!((after[2] - before[2]).abs() < 1e-4, "{:?} {:?}", before, after);
+        // and sits on the edge of the target
+        let mut target = proofed;
+        srgb_to_lrgb(&mut target);
+        let target = narrow.map(|row| row.iter().zip(target).map(|(m, c)| *m as f64 * c).sum::<f64>());
+        assert!(target.iter().all(|c| (-1e-5..=1.0 + 1e-5).contains(c)), "{:?}", target);
+        assert!(
+            target.iter().any(|c| c.abs() < 1e-4 || (c - 1.0).abs() < 1e-4),
+            "{:?}",
+            target
+        );
+    }
+
+    // sRGB itself changes nothing
+    let identity = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    let mut red = [1.0f32, 0.0, 0.0];
+    soft_proof(&mut red, identity);
+    assert_eq!(red, [1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn harmonies() {
+    // muted enough that the complement needs no gamut mapping
+    let base = [0.6f64, 0.45, 0.4];
+    let colors = harmony(&base, HarmonyScheme::Complementary);
+    assert_eq!(colors.len(), 2);
+    assert_eq!(colors[0], base);
+    let [mut a, mut b] = [colors[0], colors[1]];
+    convert_space(Space::SRGB, Space::OKLCH, &mut a);
+    convert_space(Space::SRGB, Space::OKLCH, &mut b);
+    let diff = (b[2] - a[2]).rem_euclid(360.0);
+    assert!((diff - 180.0).abs() < 1e-3, "{:?} {:?}", a, b);
+    assert!(
+        (a[0] - b[0]).abs() < 1e-3 && (a[1] - b[1]).abs() < 1e-3,
+        "{:?} {:?}",
+        a,
+        b
+    );
+
+    let base = [0.8, 0.3, 0.2];
+
+    for (scheme, count) in [
+        (HarmonyScheme::Triadic, 3),
+        (HarmonyScheme::Analogous, 3),
+        (HarmonyScheme::Tetradic, 4),
+        (HarmonyScheme::SplitComplementary, 3),
+    ] {
+        let colors = harmony(&base, scheme);
+        assert_eq!(colors.len(), count, "{:?}", scheme);
+        for color in colors {
+            assert!(
+                color.iter().all(|c| (0.0..=1.0).contains(c)),
+                "{:?} {:?}",
+                scheme,
+                color
+            );
+        }
+    }
+}
+
+#[test]
+fn ramps() {
+    let base = [0.2f64, 0.4, 0.8];
+    let lightness = |ramp: &[[f64; 3]]| -> Vec<[f64; 3]> {
+        ramp.iter()
+            .map(|p| {
+                let mut lab = *p;
+                convert_space(Space::SRGB, Space::OKLAB, &mut lab);
+                lab
+            })
+            .collect()
+    };
+
+    let tint = tints(&base, 9);
+    assert_eq!(tint.len(), 9);
+    pix_cmp(&tint[..1], &[base], 1e-5, &[]);
+    let lab = lightness(&tint);
+    assert!(lab.windows(2).all(|w| w[0][0] < w[1][0]), "{:?}", lab);
+    assert!(tint.iter().flatten().all(|c| (0.0..=1.0).contains(c)));
+
+    let shade = shades(&base, 5);
+    let lab = lightness(&shade);
+    assert!(lab.windows(2).all(|w| w[0][0] > w[1][0]), "{:?}", lab);
+
+    let tone = tones(&base, 5);
+    let lch: Vec<[f64; 3]> = tone
+        .iter()
+        .map(|p| {
+            let mut lch = *p;
+            convert_space(Space::SRGB, Space::OKLCH, &mut lch);
+            lch
+        })
+        .collect();
+    assert!(
+        lch.windows(2)
+            .all(|w| w[0][1] > w[1][1] && (w[0][0] - w[1][0]).abs() < 1e-6),
+        "{:?}",
+        lch
+    );
+
+    assert!(tints(&base, 0).is_empty());
+}
+
+#[test]
+fn duotones() {
+    let (dark, light) = ([0.1, 0.05, 0.3f64], [1.0, 0.85, 0.4]);
+    let mut black = [0.0; 3];
+    duotone(&mut black, &dark, &light);
+    pix_cmp(&[black], &[dark], 1e-6, &[]);
+    let mut white = [1.0; 3];
+    duotone(&mut white, &dark, &light);
+    pix_cmp(&[white], &[light], 1e-5, &[]);
+
+    // only lightness places the input on the ramp
+    let mut gray = [0.5; 3];
+    let mut red = [0.5, 0.0, 0.0];
+    duotone(&mut gray, &dark, &light);
+    duotone(&mut red, &dark, &light);
+    let lightness = |p: [f64; 3]| extract_channel(&p, Space::OKLAB, 0);
+    assert!(lightness(red) < lightness(gray) && lightness(gray) < lightness(light));
+}
+
+#[test]
+fn mixing() {
+    // missing hue takes the other color's hue
+    let (_, missing) = str2col::<f64, 3>("oklch(0.5 0.2 none)").unwrap();
+    let mixed = mix(&missing, &[0.7, 0.1, 120.0], 0.5, Space::OKLCH);
+    pix_cmp(&[mixed], &[[0.6, 0.15, 120.0]], 1e-6, &[]);
+    let mixed = mix(&[0.7, 0.1, 120.0], &missing, 0.25, Space::OKLCH);
+    pix_cmp(&[mixed], &[[0.65, 0.125, 120.0]], 1e-6, &[]);
+
+    // missing from both stays missing
+    let mixed = mix(&missing, &missing, 0.5, Space::OKLCH);
+    assert!(mixed[2].is_nan() && mixed[0] == 0.5);
+
+    // shorter hue arc
+    let mixed = mix(&[0.5f32, 0.1, 350.0, 1.0], &[0.5, 0.1, 30.0, 0.0], 0.5, Space::OKLCH);
+    assert_eq!(mixed, [0.5, 0.1, 10.0, 0.5]);
+    let mixed = mix(&[0.9f32, 0.5, 0.5], &[0.1, 0.5, 0.5], 0.5, Space::HSV);
+    assert!(
+        (mixed[0] - 0.0).abs() < 1e-6 || (mixed[0] - 1.0).abs() < 1e-6,
+        "{:?}",
+        mixed
+    );
+    // no hue in rectangular spaces
+    assert_eq!(
+        mix(&[0.0f32, -0.2, 0.2], &[1.0, 0.2, -0.2], 0.5, Space::OKLAB),
+        [0.5, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn color_mix_css() {
+    let nan = f64::NAN;
+    let (red, blue) = ([1.0, 0.0, 0.0], [0.0, 0.0, 1.0]);
+    let mixed = |pa, pb| color_mix(Space::SRGB, red, pa, blue, pb, HueInterpolation::Shorter).unwrap();
+    // color-mix(in srgb, red 30%, blue)
+    pix_cmp(&[mixed(30.0, nan)], &[[0.3, 0.0, 0.7]], 1e-12, &[]);
+    // omitted, explicit, and over 100% are all normalized alike
+    for (pa, pb) in [(nan, nan), (50.0, nan), (nan, 50.0), (80.0, 80.0)] {
+        pix_cmp(&[mixed(pa, pb)], &[[0.5, 0.0, 0.5]], 1e-12, &[]);
+    }
+
+    // color-mix(in srgb, rgb(100% 0% 0% / 0.7) 25%, rgb(0% 100% 0% / 0.2)) = rgb(53.846% 46.154% 0% / 0.325)
+    let (red, green) = ([1.0, 0.0, 0.0, 0.7], [0.0, 1.0, 0.0, 0.2]);
+    let result = color_mix(Space::SRGB, red, 25.0, green, nan, HueInterpolation::Shorter).unwrap();
+    let expected = [0.538462, 0.461538, 0.0, 0.325];
+    result
+        .iter()
+        .zip(expected)
+        .for_each(|(r, e)| assert!((r - e).abs() < 1e-6, "{:?}", result));
+    // same at 20% 60%, but the missing 20% scales alpha to 0.26
+    let result = color_mix(Space::SRGB, red, 20.0, green, 60.0, HueInterpolation::Shorter).unwrap();
+    let expected = [0.538462, 0.461538, 0.0, 0.26];
+    result
+        .iter()
+        .zip(expected)
+        .for_each(|(r, e)| assert!((r - e).abs() < 1e-6, "{:?}", result));
+
+    // hue methods between 20 and 340 degrees
+    let (a, b) = ([0.7, 0.1, 20.0], [0.7, 0.1, 340.0]);
+    for (method, ab, ba) in [
+        (HueInterpolation::Shorter, 0.0, 0.0),
+        (HueInterpolation::Longer, 180.0, 180.0),
+        (HueInterpolation::Increasing, 180.0, 0.0),
+        (HueInterpolation::Decreasing, 0.0, 180.0),
+    ] {
+        let forward = color_mix(Space::OKLCH, a, nan, b, nan, method).unwrap();
+        let backward = color_mix(Space::OKLCH, b, nan, a, nan, method).unwrap();
+        assert!((forward[2] - ab).abs() < 1e-9, "{:?} {:?}", method, forward);
+        assert!((backward[2] - ba).abs() < 1e-9, "{:?} {:?}", method, backward);
+    }
+
+    // missing hue takes the other's
+    let gray = [0.5, 0.0, nan];
+    assert_eq!(
+        color_mix(Space::OKLCH, gray, nan, a, nan, HueInterpolation::Shorter).unwrap()[2],
+        20.0
+    );
+
+    assert_eq!(
+        color_mix(Space::SRGB, blue, 0.0, blue, 0.0, HueInterpolation::Shorter),
+        None
+    );
+    assert_eq!(
+        color_mix(Space::SRGB, blue, -10.0, blue, 50.0, HueInterpolation::Shorter),
+        None
+    );
+}
+
+#[test]
+fn spline_gradients() {
+    let stops = [
+        [0.3f64, 0.1, 30.0],
+        [0.6, 0.15, 140.0],
+        [0.8, 0.05, 250.0],
+        [0.5, 0.12, 20.0],
+    ];
+    let gradient = spline_gradient(&stops, 3 * 5 + 1, Space::OKLCH);
+    assert_eq!(gradient.len(), 16);
+    for (n, stop) in stops.iter().enumerate() {
+        let sample = gradient[n * 5];
+        pix_cmp(&[sample], &[*stop], 1e-12, &[]);
+    }
+    // monotone: never overshoots the neighboring stops
+    for (n, pair) in stops.windows(2).enumerate() {
+        for sample in &gradient[n * 5..=n * 5 + 5] {
+            let (lo, hi) = (pair[0][0].min(pair[1][0]), pair[0][0].max(pair[1][0]));
+            assert!(sample[0] >= lo - 1e-12 && sample[0] <= hi + 1e-12, "{:?}", sample);
+        }
+    }
+    // 250 -> 20 takes the short way through 360
+    assert!(
+        gradient[13..15].iter().all(|p| p[2] > 250.0 || p[2] < 20.0),
+        "{:?}",
+        &gradient[13..15]
+    );
+
+    // a straight line in OKLAB stays straight
+    let line = spline_gradient(&[[0.0f32, 0.0, 0.0], [0.5, 0.1, 0.1], [1.0, 0.2, 0.2]], 5, Space::OKLAB);
+    assert!(
+        line.iter()
+            .enumerate()
+            .all(|(n, p)| (p[0] - n as f32 / 4.0).abs() < 1e-6),
+        "{:?}",
+        line
+    );
+
+    assert!(spline_gradient::<f32>(&[], 4, Space::OKLAB).is_empty());
+    assert_eq!(spline_gradient(&[[0.5f32; 3]], 3, Space::OKLAB), vec![[0.5; 3]; 3]);
+}
+
+#[test]
+fn hue_interpolation() {
+    // (method, 20 -> 340 at t 0.25, 340 -> 20 at t 0.25)
+    for (method, forward, backward) in [
+        (HueInterpolation::Shorter, 10.0, 350.0),
+        (HueInterpolation::Longer, 100.0, 260.0),
+        (HueInterpolation::Increasing, 100.0, 350.0),
+        (HueInterpolation::Decreasing, 10.0, 260.0),
+    ] {
+        let (f, b) = (
+            interpolate_hue(20.0, 340.0, 0.25, method),
+            interpolate_hue(340.0, 20.0, 0.25, method),
+        );
+        assert!((f - forward).abs() < 1e-9, "{:?} {}", method, f);
+        assert!((b - backward).abs() < 1e-9, "{:?} {}", method, b);
+        assert_eq!(interpolate_hue(20.0, 340.0, 0.0f32, method), 20.0);
+        assert!((interpolate_hue(20.0, 340.0, 1.0f64, method) - 340.0).abs() < 1e-9);
+    }
+    // exactly opposite hues move up whichever comes first
+    assert_eq!(interpolate_hue(100.0, 280.0, 0.5f32, HueInterpolation::Shorter), 190.0);
+    assert_eq!(interpolate_hue(280.0, 100.0, 0.5f32, HueInterpolation::Shorter), 10.0);
+    assert_eq!(
+        mix(&[0.7, 0.1, 280.0f32], &[0.7, 0.1, 100.0], 0.5, Space::OKLCH)[2],
+        10.0
+    );
+    let spline = spline_gradient(&[[0.7, 0.1, 280.0f32], [0.7, 0.1, 100.0]], 3, Space::OKLCH);
+    assert_eq!(spline[1][2], 10.0);
+
+    // out of range inputs are wrapped first
+    assert!((interpolate_hue(-340.0, 700.0, 0.5f64, HueInterpolation::Shorter) - 0.0).abs() < 1e-9);
+
+    // mixing follows the method, with HSV's hue period of 1.0
+    let (a, b) = ([0.05, 1.0, 1.0f64], [0.95, 1.0, 1.0]);
+    assert!((mix_hue(&a, &b, 0.5, Space::HSV, HueInterpolation::Shorter)[0]).abs() < 1e-9);
+    assert!((mix_hue(&a, &b, 0.5, Space::HSV, HueInterpolation::Longer)[0] - 0.5).abs() < 1e-9);
+    assert_eq!(
+        mix(&a, &b, 0.5, Space::HSV),
+        mix_hue(&a, &b, 0.5, Space::HSV, HueInterpolation::Shorter)
+    );
+    let (a, b) = ([0.7, 0.1, 20.0f32], [0.7, 0.1, 340.0]);
+    assert_eq!(
+        mix_hue(&a, &b, 0.25, Space::OKLCH, HueInterpolation::Increasing)[2],
+        100.0
+    );
+}
+
+#[test]
+fn palette() {
+    let mut palette = Palette::new(
+        vec![
+            [0.8f64, 0.2, 0.2],
+            [0.1, 0.1, 0.1],
+            [0.801, 0.2, 0.199],
+            [0.9, 0.9, 0.9],
+            [0.1, 0.1, 0.102],
+        ],
+        Space::SRGB,
+    );
+    palette.dedup_perceptual(0.01);
+    assert_eq!(palette.colors, vec![[0.8, 0.2, 0.2], [0.1, 0.1, 0.1], [0.9, 0.9, 0.9]]);
+    palette.dedup_perceptual(0.0);
+    assert_eq!(palette.colors.len(), 3);
+
+    assert_eq!(palette.nearest(&[0.95, 0.95, 0.9]), Some(2));
+    assert_eq!(palette.nearest(&[0.7, 0.1, 0.3]), Some(0));
+    assert_eq!(Palette::<f32>::new(vec![], Space::SRGB).nearest(&[0.5; 3]), None);
+
+    palette.sort_by_lightness();
+    assert_eq!(palette.colors, vec![[0.1, 0.1, 0.1], [0.8, 0.2, 0.2], [0.9, 0.9, 0.9]]);
+
+    // stored in another space
+    let mut lch = palette.clone();
+    lch.colors
+        .iter_mut()
+        .for_each(|c| convert_space(Space::SRGB, Space::OKLCH, c));
+    lch.space = Space::OKLCH;
+    assert_eq!(lch.nearest(&[0.95, 0.95, 0.9]), Some(2));
+
+    lch.extend_harmony(HarmonyScheme::Complementary);
+    assert_eq!(lch.colors.len(), 6);
+    let mut complement = lch.colors[4];
+    convert_space(Space::OKLCH, Space::SRGB, &mut complement);
+    pix_cmp(
+        &[complement],
+        &[harmony(&[0.8, 0.2, 0.2], HarmonyScheme::Complementary)[1]],
+        1e-6,
+        &[],
+    );
+}
+
+#[test]
+fn space_classification() {
+    for space in Space::ALL {
+        assert!(!space.is_scene_referred(), "{}", space);
+        // bounded spaces never leave 0..=1 over the sRGB gamut
+        let quants = space.srgb_quants();
+        if space.is_bounded() {
+            for (lo, hi) in quants[0].iter().zip(quants[100].iter()) {
+                assert!(*lo >= 0.0 || lo.is_infinite(), "{}", space);
+                assert!(*hi <= 1.0 + 1e-6 || hi.is_infinite(), "{}", space);
+            }
+        }
+    }
+    assert!(Space::SRGB.is_bounded());
+    assert!(Space::HSV.is_bounded());
+    assert!(!Space::XYZ.is_bounded());
+    assert!(!Space::OKLCH.is_bounded());
+}
+
+#[test]
+fn averaging() {
+    let avg = average_linear(&[[0.0f32; 3], [1.0; 3]]);
+    assert!(avg.iter().all(|c| (c - 0.7354).abs() < 1e-3), "{:?}", avg);
+    assert!((srgb_eotf(avg[0]) - 0.5).abs() < 1e-5);
+
+    // fully transparent pixels carry no color
+    let avg = average_linear(&[[1.0f64, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 0.0]]);
+    assert!((avg[0] - 1.0).abs() < 1e-6 && avg[1].abs() < 1e-6, "{:?}", avg);
+    assert_eq!(avg[3], 0.5);
+
+    let avg = average_linear(&[[0.2f32, 0.35, 0.95, 0.35]; 5]);
+    pix_cmp(
+        &[avg.map(|c| c as f64)[..3].try_into().unwrap()],
+        &[[0.2, 0.35, 0.95]],
+        1e-5,
+        &[],
+    );
+    assert!((avg[3] - 0.35).abs() < 1e-6);
+
+    assert_eq!(average_linear::<f32, 4>(&[]), [0.0; 4]);
+    assert_eq!(average_linear(&[[0.5f32, 0.5, 0.5, 0.0]]), [0.0; 4]);
+}
+
 #[test]
 fn individual() {
     let runs: &[(&str, &[[f64; 3]], &[[f64; 3]], fn(pixel: &mut [f64; 3]))] = &[
@@ -245,7 +1494,7 @@ fn individual() {
         ("CIELAB->XYZ", CIELAB, XYZ, cielab_to_xyz),
         ("XYZ->OKLAB", XYZ, OKLAB, xyz_to_oklab),
         ("OKLAB->XYZ", OKLAB, XYZ, oklab_to_xyz),
-        //("XYZ->JZAZBZ", XYZ, JZAZBZ, xyz_to_jzazbz),
+        ("XYZ->JZAZBZ", XYZ, JZAZBZ, xyz_to_jzazbz),
         ("JZAZBZ->XYZ", JZAZBZ, XYZ, jzazbz_to_xyz),
         ("CIELAB->CIELCH", CIELAB, CIELCH, lab_to_lch),
         ("CIELCH->CIELAB", CIELCH, CIELAB, lch_to_lab),
@@ -257,11 +1506,23 @@ fn individual() {
         input.iter_mut().for_each(|p| (*func)(p));
         pix_cmp(&input, to, 1e-3, &[]);
     }
+}
+
+#[test]
+fn jzazbz_reference() {
+    // f64 matrices keep even the thousands of chroma on 9 within the reference digits
+    let mut input = XYZ.to_vec();
+    input.iter_mut().for_each(xyz_to_jzazbz);
+    pix_cmp(&input, JZAZBZ, 1e-4, &[]);
+
+    let mut input = JZAZBZ.to_vec();
+    input.iter_mut().for_each(jzazbz_to_xyz);
+    pix_cmp(&input, XYZ, 1e-5, &[]);
 
-    println!("XYZ->JZAZBZ");
     let mut input = XYZ.to_vec();
-    input.iter_mut().for_each(|p| xyz_to_jzazbz(p));
-    pix_cmp(&input, JZAZBZ, 1e-2, &[]);
+    convert_space_chunked::<f64, 3>(Space::XYZ, Space::JZCZHZ, &mut input);
+    // skip places where hue can wrap
+    pix_cmp(&input, JZCZHZ, 1e-4, &[0, 1, 7]);
 }
 
 #[test]
@@ -269,12 +1530,18 @@ fn inversions() {
     let runs: &[(&[[f64; 3]], fn(pixel: &mut [f64; 3]), fn(pixel: &mut [f64; 3]), &str)] = &[
         (SRGB, srgb_to_hsv, hsv_to_srgb, "HSV"),
         (SRGB, srgb_to_lrgb, lrgb_to_srgb, "LRGB"),
-        (LRGB, lrgb_to_xyz, xyz_to_lrgb, "XYZ"),         // 1e-4
+        (LRGB, lrgb_to_xyz, xyz_to_lrgb, "XYZ"), // 1e-4
+        (LRGB, lrgb_to_xyz_precise, xyz_to_lrgb_precise, "XYZ_PRECISE"),
         (LRGB, _lrgb_to_ictcp, _ictcp_to_lrgb, "ICTCP"), // 1e-4
         (XYZ, xyz_to_cielab, cielab_to_xyz, "CIELAB"),
         (XYZ, xyz_to_oklab, oklab_to_xyz, "OKLAB"),    // 1e-3
         (XYZ, xyz_to_jzazbz, jzazbz_to_xyz, "JZAZBZ"), // 1e-4
         (CIELAB, lab_to_lch, lch_to_lab, "LCH"),
+        (LRGB, lrgb_to_oklab, oklab_to_lrgb, "LRGB_OKLAB"),
+        (LRGB, lrgb_to_lp3, lp3_to_lrgb, "LP3"),
+        (OKLAB, oklab_to_oklrab, oklrab_to_oklab, "OKLRAB"),
+        (LRGB, lrgb_to_lrec2020, lrec2020_to_lrgb, "LREC2020"),
+        (LRGB, lp3_to_lrec2020, lrec2020_to_lp3, "LP3_LREC2020"),
     ];
     for (pixel, fwd, bwd, label) in runs.iter() {
         let mut owned = pixel.to_vec();
@@ -286,6 +1553,561 @@ fn inversions() {
         pix_cmp(&owned, pixel, 1e-3, &[]);
     }
 }
+#[test]
+fn jzazbz_scaled() {
+    let mut pixel = [1.0, 0.0, 0.0, 0.5];
+    convert_space(Space::SRGB, Space::JZAZBZ, &mut pixel);
+    let raw = pixel;
+    jzazbz_scale(&mut pixel, JZAZBZ_SCALE.to_dt());
+    assert!(pixel[0] > 1e-1 && pixel[0] < 1.0, "{:?}", pixel);
+    assert_eq!(pixel[3], 0.5);
+    pix_cmp(
+        &[[pixel[0], pixel[1], pixel[2]]],
+        &[[raw[0] * 100.0, raw[1] * 100.0, raw[2] * 100.0]],
+        1e-12,
+        &[],
+    );
+    jzazbz_unscale(&mut pixel, JZAZBZ_SCALE.to_dt());
+    convert_space(Space::JZAZBZ, Space::SRGB, &mut pixel);
+    pix_cmp(&[[pixel[0], pixel[1], pixel[2]]], &[[1.0, 0.0, 0.0]], 1e-6, &[]);
+    assert_eq!(pixel[3], 0.5);
+}
+
+#[test]
+fn nits() {
+    let mut pixel = [0.4124, 0.2126, 0.0193, 0.5];
+    scale_to_nits(&mut pixel, 203.0);
+    assert!((pixel[1] - 0.2126 * 203.0).abs() < 1e-12);
+    assert_eq!(pixel[3], 0.5);
+    scale_from_nits(&mut pixel, 203.0);
+    pix_cmp(
+        &[[pixel[0], pixel[1], pixel[2]]],
+        &[[0.4124, 0.2126, 0.0193]],
+        1e-12,
+        &[],
+    );
+    assert_eq!(pixel[3], 0.5);
+
+    // brighter absolute white means higher Jz
+    let (mut relative, mut absolute) = ([0.9505f32, 1.0, 1.089], [0.9505f32, 1.0, 1.089]);
+    scale_to_nits(&mut absolute, 100.0);
+    xyz_to_jzazbz(&mut relative);
+    xyz_to_jzazbz(&mut absolute);
+    assert!(absolute[0] > relative[0] * 5.0, "{:?} {:?}", relative, absolute);
+}
+
+#[test]
+fn f64_constants() {
+    // formulas written out with full f64 constants
+    let srgb = |n: f64| ((n + 0.055) / 1.055).powf(2.4);
+    // JzAzBz's modified PQ
+    let pqz = |f: f64| {
+        let y = (f / 10000.0).powf(2610.0 / 16384.0);
+        ((3424.0 / 4096.0 + 2413.0 / 128.0 * y) / (1.0 + 2392.0 / 128.0 * y)).powf(1.7 * 2523.0 / 32.0)
+    };
+    for n in [0.1f64, 0.25, 0.5, 0.75, 1.0] {
+        assert!((srgb_eotf(n) - srgb(n)).abs() < 1e-14, "{} {}", srgb_eotf(n), srgb(n));
+        assert!((srgb_oetf(srgb(n)) - n).abs() < 1e-14, "{} {}", srgb_oetf(srgb(n)), n);
+        let f = n * 10000.0;
+        assert!((pqz_oetf(f) - pqz(f)).abs() < 1e-14, "{} {}", pqz_oetf(f), pqz(f));
+        assert!((pqz_eotf(pqz(f)) - f).abs() < 1e-9, "{} {}", pqz_eotf(pqz(f)), f);
+    }
+}
+
+#[test]
+fn dtype_clamp() {
+    fn check<T: DType>() {
+        let (lo, hi) = (T::ff32(-0.5), T::ff32(2.0));
+        assert_eq!(DType::clamp(T::ff32(-3.0), lo, hi), lo);
+        assert_eq!(DType::clamp(T::ff32(1.25), lo, hi), T::ff32(1.25));
+        assert_eq!(DType::clamp(T::ff32(9.0), lo, hi), hi);
+        assert!(DType::clamp(T::ff32(f32::NAN), lo, hi).is_nan());
+    }
+    check::<f32>();
+    check::<f64>();
+    assert_eq!(srgb_to_irgb([-0.2, 0.5, 1.3f32]), [0, 128, 255]);
+    assert_eq!(srgb_to_irgb([f32::NAN, 0.5, 1.3]), [0, 128, 255]);
+
+    // sanitizing functions still send NaN to the lower bound
+    assert_eq!(blackbody_xyz(f64::NAN), blackbody_xyz(1667.0));
+    let mut pixel = [f32::NAN, 0.1, 0.1];
+    clamp_to_gamut_ranges(&mut pixel, Space::OKLAB);
+    assert_eq!(pixel[0], Space::OKLAB.srgb_quants()[0][0]);
+    let mut pixel = [0.5f64, 0.5, 0.5];
+    adjust_exposure(&mut pixel, f64::NAN, true);
+    assert_eq!(pixel, [0.0; 3]);
+    let identity = CubeLut::parse(&write_cube_lut(
+        &generate_lut::<f32>(Space::SRGB, Space::SRGB, 2)[..],
+        Space::SRGB,
+    ))
+    .unwrap();
+    let mut pixel = [f32::NAN, 0.5, 1.0];
+    apply_cube_lut(&mut pixel, &identity);
+    assert_eq!(pixel, [0.0, 0.5, 1.0]);
+}
+
+#[test]
+fn dtype_lerp() {
+    fn check<T: DType>() {
+        let (a, b) = (T::ff32(-2.0), T::ff32(6.0));
+        assert_eq!(DType::lerp(a, b, T::ff32(0.0)), a);
+        assert_eq!(DType::lerp(a, b, T::ff32(1.0)), b);
+        assert_eq!(DType::lerp(a, b, T::ff32(0.5)), T::ff32(2.0));
+        assert_eq!(DType::lerp(a, b, T::ff32(0.25)), T::ff32(0.0));
+        assert_eq!(DType::lerp(a, b, T::ff32(1.5)), T::ff32(10.0));
+        assert_eq!(DType::lerp(b, a, T::ff32(0.5)), T::ff32(2.0));
+    }
+    check::<f32>();
+    check::<f64>();
+}
+
+#[test]
+fn mm_compensation() {
+    let chain = [XYZ65_MAT, OKLAB_M1, OKLAB_M1_INV, XYZ65_MAT_INV];
+    let (mut plain_err, mut compensated_err) = (0.0, 0.0);
+    for n in 0..1000 {
+        let pixel = [n % 10, n / 10 % 10, n / 100].map(|c| (c as f32 + 0.37) / 10.0);
+        let reference = chain.iter().fold(pixel.map(|c| c as f64), |p, m| mm_compensated(*m, p));
+        let plain = chain.iter().fold(pixel, |p, m| mm_fma(*m, p));
+        let compensated = chain.iter().fold(pixel, |p, m| mm_compensated(*m, p));
+        for c in 0..3 {
+            plain_err += (plain[c] as f64 - reference[c]).abs();
+            compensated_err += (compensated[c] as f64 - reference[c]).abs();
+        }
+    }
+    assert!(compensated_err < plain_err * 0.8, "{} {}", compensated_err, plain_err);
+}
+
+#[test]
+fn transfer_functions() {
+    let functions = [
+        TransferFunction::Srgb,
+        TransferFunction::Pq,
+        TransferFunction::Hlg,
+        TransferFunction::Bt709,
+        TransferFunction::Gamma(2.2),
+        TransferFunction::Linear,
+    ];
+    for function in functions {
+        let scale = if function == TransferFunction::Pq { 10000.0 } else { 1.0 };
+        for n in 0..=20 {
+            let linear = n as f64 / 20.0 * scale;
+            let signal = function.apply(linear);
+            assert!(
+                (0.0..=1.0 + 1e-9).contains(&signal),
+                "{:?} {} {}",
+                function,
+                linear,
+                signal
+            );
+            let back = function.invert(signal);
+            assert!(
+                (back - linear).abs() < 1e-9 * scale,
+                "{:?} {} {}",
+                function,
+                linear,
+                back
+            );
+            let back32 = function.invert(function.apply(linear as f32));
+            assert!(
+                (back32 as f64 - linear).abs() < 1e-4 * scale,
+                "{:?} {} {}",
+                function,
+                linear,
+                back32
+            );
+        }
+    }
+    assert_eq!(TransferFunction::Srgb.apply(0.5f32), srgb_oetf(0.5));
+    // known points
+    assert!((hlg_oetf(1.0f64) - 1.0).abs() < 1e-6);
+    assert!((hlg_oetf(1.0f64 / 12.0) - 0.5).abs() < 1e-12);
+    assert!((bt709_oetf(1.0f64) - 1.0).abs() < 1e-12);
+    assert!((bt709_oetf(0.018f64) - 0.081).abs() < 1e-3);
+    assert_eq!(TransferFunction::Linear.apply(0.3f32), 0.3);
+    assert!((TransferFunction::Gamma(2.0).invert(0.5f64) - 0.25).abs() < 1e-9);
+}
+
+#[test]
+fn aces_log() {
+    type Codec<F> = (fn(F) -> F, fn(F) -> F);
+    let pairs: [Codec<f64>; 2] = [(acescc_encode, acescc_decode), (acescct_encode, acescct_decode)];
+    let pairs32: [Codec<f32>; 2] = [(acescc_encode, acescc_decode), (acescct_encode, acescct_decode)];
+    for ((encode, decode), (encode32, decode32)) in pairs.into_iter().zip(pairs32) {
+        // 2^-15 up to the f16 max, covering the ACEScc linear segment and ACEScct toe
+        for n in -60..=63 {
+            let linear = 2f64.powf(n as f64 / 4.0);
+            let back = decode(encode(linear));
+            assert!((back - linear).abs() <= linear * 1e-9, "{} {}", linear, back);
+            let back32 = decode32(encode32(linear as f32));
+            assert!((back32 as f64 - linear).abs() <= linear * 1e-4, "{} {}", linear, back32);
+        }
+        assert!(decode(encode(0.0)).abs() < 1e-15);
+        assert!(encode(1e-7) < encode(1e-6) && encode(1e-6) < encode(0.18));
+        assert_eq!(decode(10.0), 65504.0);
+    }
+    // published points
+    assert!((acescc_encode(0.18f64) - 0.413_588_402_492_442_3).abs() < 1e-12);
+    assert!((acescct_encode(0.18f64) - 0.413_588_402_492_442_3).abs() < 1e-12);
+    assert!((acescc_encode(0.0f64) - -0.358_447_488_584_474_84).abs() < 1e-12);
+    assert!((acescct_encode(0.0f64) - 0.072_905_534_195_835_5).abs() < 1e-12);
+    assert!((acescct_encode(0.0078125f64) - 0.155_251_141_552_511).abs() < 1e-9);
+    assert_eq!(TransferFunction::AcesCc.apply(0.5f32), acescc_encode(0.5));
+    assert_eq!(TransferFunction::AcesCct.invert(0.5f32), acescct_decode(0.5));
+}
+
+#[test]
+fn srgb_transfer_fast() {
+    let steps = 100000;
+    for n in 0..=steps {
+        let n = n as f64 / steps as f64;
+        let (eotf, eotf_fast) = (srgb_eotf(n), srgb_eotf_fast(n));
+        let (oetf, oetf_fast) = (srgb_oetf(n), srgb_oetf_fast(n));
+        assert!(
+            (eotf - eotf_fast).abs() < 0.5 / 255.0,
+            "EOTF {}: {} != {}",
+            n,
+            eotf,
+            eotf_fast
+        );
+        assert!(
+            (oetf - oetf_fast).abs() < 0.5 / 255.0,
+            "OETF {}: {} != {}",
+            n,
+            oetf,
+            oetf_fast
+        );
+        let n = n as f32;
+        assert!((srgb_eotf(n) - srgb_eotf_fast(n)).abs() < 0.5 / 255.0, "EOTF F32 {}", n);
+        assert!((srgb_oetf(n) - srgb_oetf_fast(n)).abs() < 0.5 / 255.0, "OETF F32 {}", n);
+    }
+}
+
+#[test]
+fn oklab_direct() {
+    let mut fwd = LRGB.to_vec();
+    fwd.iter_mut().for_each(lrgb_to_oklab);
+    let mut two_step = LRGB.to_vec();
+    two_step.iter_mut().for_each(|p| {
+        lrgb_to_xyz(p);
+        xyz_to_oklab(p);
+    });
+    pix_cmp(&fwd, &two_step, 1e-4, &[]);
+    pix_cmp(&fwd, OKLAB, 1e-3, &[]);
+
+    let mut bwd = OKLAB.to_vec();
+    bwd.iter_mut().for_each(oklab_to_lrgb);
+    let mut two_step = OKLAB.to_vec();
+    two_step.iter_mut().for_each(|p| {
+        oklab_to_xyz(p);
+        xyz_to_lrgb(p);
+    });
+    pix_cmp(&bwd, &two_step, 1e-4, &[]);
+    pix_cmp(&bwd, LRGB, 1e-3, &[]);
+}
+
+#[test]
+fn batched_xyz() {
+    let mut scalar = LRGB.to_vec();
+    scalar.iter_mut().for_each(lrgb_to_xyz);
+    let mut batched = LRGB.to_vec();
+    batched
+        .chunks_exact_mut(4)
+        .for_each(|chunk| lrgb_to_xyz_x4(chunk.try_into().unwrap()));
+    let whole = LRGB.len() / 4 * 4;
+    batched[whole..].iter_mut().for_each(lrgb_to_xyz);
+    pix_cmp(&batched, &scalar, 1e-12, &[]);
+
+    let mut alpha = [
+        [0.2f32, 0.4, 0.6, 0.1],
+        [1.0, 1.0, 1.0, 0.2],
+        [0.0; 4],
+        [0.5, 0.0, 0.25, 1.0],
+    ];
+    let mut reference = alpha;
+    lrgb_to_xyz_x4(&mut alpha);
+    reference.iter_mut().for_each(lrgb_to_xyz);
+    assert_eq!(alpha.map(|p| p[3]), [0.1, 0.2, 0.0, 1.0]);
+    assert!(
+        alpha
+            .iter()
+            .flatten()
+            .zip(reference.iter().flatten())
+            .all(|(a, b)| (a - b).abs() < 1e-6),
+        "{:?}",
+        alpha
+    );
+}
+
+#[test]
+fn oklab_toe() {
+    // <https://bottosson.github.io/posts/colorpicker/#intermission---a-new-lightness-estimate-for-oklab>
+    let lr: Vec<[f64; 3]> = [0.0, 0.1, 0.25, 0.5, 0.75, 1.0]
+        .into_iter()
+        .map(|l| {
+            let mut p = [l, 0.1, -0.05];
+            oklab_to_oklrab(&mut p);
+            p
+        })
+        .collect();
+    pix_cmp(
+        &lr,
+        &[
+            [0.0, 0.1, -0.05],
+            [0.029631373306766642, 0.1, -0.05],
+            [0.14661412944746693, 0.1, -0.05],
+            [0.42114056260896976, 0.1, -0.05],
+            [0.7092972572794943, 0.1, -0.05],
+            [1.0, 0.1, -0.05],
+        ],
+        1e-12,
+        &[],
+    );
+    let mut back = [0.42114056260896976f32, 0.0, 0.0, 0.5];
+    oklrab_to_oklab(&mut back);
+    assert!((back[0] - 0.5).abs() < 1e-6, "{:?}", back);
+    assert_eq!(back[3], 0.5);
+}
+
+#[test]
+fn display_p3() {
+    let mut red = [1.0f64, 0.0, 0.0];
+    lrgb_to_lp3(&mut red);
+    // still reddest but no longer pure, as sRGB red sits inside the P3 triangle
+    assert!(red[0] > 0.8 && red[0] < 1.0, "{:?}", red);
+    assert!(red[1] > 0.0 && red[2] > 0.0, "{:?}", red);
+    // sRGB primaries stay in gamut
+    for n in 0..3 {
+        let mut p = [0.0f64; 3];
+        p[n] = 1.0;
+        lrgb_to_lp3(&mut p);
+        assert!(p.iter().all(|c| (0.0..=1.0).contains(c)), "{:?}", p);
+    }
+    let mut white = [1.0f32, 1.0, 1.0, 0.5];
+    lrgb_to_lp3(&mut white);
+    assert!(white.iter().take(3).all(|c| (c - 1.0).abs() < 1e-6), "{:?}", white);
+    assert_eq!(white[3], 0.5);
+    // matches the route through CIE XYZ
+    let mut direct = LRGB.to_vec();
+    direct.iter_mut().for_each(lrgb_to_lp3);
+    let mut via = LRGB.to_vec();
+    via.iter_mut().for_each(|p| {
+        lrgb_to_xyz_precise(p);
+        *p = mm(
+            t([
+                [2.4934969, -0.9313836, -0.4027108],
+                [-0.8294890, 1.7626641, 0.0236247],
+                [0.0358458, -0.0761724, 0.9568845],
+            ]),
+            *p,
+        );
+    });
+    pix_cmp(&direct, &via, 1e-4, &[]);
+}
+
+#[test]
+fn rec2020() {
+    // ITU-R BT.2087 reference for sRGB red
+    let mut red = [1.0f64, 0.0, 0.0];
+    lrgb_to_lrec2020(&mut red);
+    pix_cmp(&[red], &[[0.6274, 0.0691, 0.0164]], 1e-4, &[]);
+    let mut white = [1.0f32, 1.0, 1.0, 0.5];
+    lp3_to_lrec2020(&mut white);
+    assert!(white.iter().take(3).all(|c| (c - 1.0).abs() < 1e-6), "{:?}", white);
+    assert_eq!(white[3], 0.5);
+    // folded matrices match the two step routes
+    let mut direct = LRGB.to_vec();
+    direct.iter_mut().for_each(lp3_to_lrec2020);
+    let mut via = LRGB.to_vec();
+    via.iter_mut().for_each(|p| {
+        lp3_to_lrgb(p);
+        lrgb_to_lrec2020(p);
+    });
+    pix_cmp(&direct, &via, 1e-4, &[]);
+    let mut direct = LRGB.to_vec();
+    direct.iter_mut().for_each(lrec2020_to_lrgb);
+    let mut via = LRGB.to_vec();
+    via.iter_mut().for_each(|p| {
+        lrec2020_to_lp3(p);
+        lp3_to_lrgb(p);
+    });
+    pix_cmp(&direct, &via, 1e-4, &[]);
+    // and the route through CIE XYZ
+    let mut direct = LRGB.to_vec();
+    direct.iter_mut().for_each(lrgb_to_lrec2020);
+    let mut via = LRGB.to_vec();
+    via.iter_mut().for_each(|p| {
+        lrgb_to_xyz_precise(p);
+        *p = mm(
+            t([
+                [1.7166512, -0.3556708, -0.2533663],
+                [-0.6666844, 1.6164812, 0.0157685],
+                [0.0176399, -0.0427706, 0.9421031],
+            ]),
+            *p,
+        );
+    });
+    pix_cmp(&direct, &via, 1e-4, &[]);
+}
+
+#[test]
+fn const_conversions() {
+    const XYZ_CONST: [f32; 3] = lrgb_to_xyz_const([0.2, 0.35, 0.95]);
+    const LRGB_CONST: [f32; 3] = xyz_to_lrgb_const(XYZ_CONST);
+
+    let mut xyz = [0.2f32, 0.35, 0.95];
+    lrgb_to_xyz(&mut xyz);
+    assert!(
+        xyz.iter().zip(XYZ_CONST.iter()).all(|(a, b)| (a - b).abs() < 1e-6),
+        "{:?} {:?}",
+        xyz,
+        XYZ_CONST
+    );
+    assert!(
+        LRGB_CONST
+            .iter()
+            .zip([0.2, 0.35, 0.95])
+            .all(|(a, b)| (a - b).abs() < 1e-5),
+        "{:?}",
+        LRGB_CONST
+    );
+}
+
+#[test]
+fn xyz_precise() {
+    let d65 = D65.map(|c| c as f64);
+
+    let mut white = [1.0f64; 3];
+    lrgb_to_xyz_precise(&mut white);
+    assert!(
+        white.iter().zip(d65.iter()).all(|(a, b)| (a - b).abs() < 1e-6),
+        "{:?} != {:?}",
+        white,
+        d65
+    );
+
+    let mut rounded = [1.0f64; 3];
+    lrgb_to_xyz(&mut rounded);
+    assert!((white[0] - d65[0]).abs() < (rounded[0] - d65[0]).abs());
+    assert!((white[2] - d65[2]).abs() < (rounded[2] - d65[2]).abs());
+
+    // luminance survives the round trip
+    for pixel in LRGB {
+        let mut xyz = *pixel;
+        lrgb_to_xyz_precise(&mut xyz);
+        let mut back = xyz;
+        xyz_to_lrgb_precise(&mut back);
+        lrgb_to_xyz_precise(&mut back);
+        assert!(
+            (xyz[1] - back[1]).abs() < 1e-6 * xyz[1].abs().max(1.0),
+            "{:?} {:?}",
+            xyz,
+            back
+        );
+    }
+}
+
+#[test]
+fn lch_hue_hint() {
+    for (space, hint) in [(Space::CIELAB, 123.0), (Space::OKLAB, 250.0), (Space::JZAZBZ, 45.0)] {
+        for v in [0.05, 0.5, 1.0] {
+            let mut pixel = [v, v, v, 0.5f32];
+            convert_space(Space::SRGB, space, &mut pixel);
+            lab_to_lch_hue_hint(&mut pixel, hint);
+            assert_eq!(pixel[2], hint, "{} {}", space, v);
+            assert_eq!(pixel[3], 0.5);
+        }
+        let mut pixel = [0.8, 0.2, 0.1f64];
+        convert_space(Space::SRGB, space, &mut pixel);
+        let mut reference = pixel;
+        lab_to_lch(&mut reference);
+        lab_to_lch_hue_hint(&mut pixel, hint as f64);
+        assert_eq!(pixel, reference, "{}", space);
+    }
+    let mut pixel = [50.0, 1e-6, -1e-6f32];
+    lab_to_lch_hue_hint(&mut pixel, 90.0);
+    assert_eq!(pixel[2], 90.0);
+    let mut pixel = [0.0, 0.0, 0.0f32];
+    lab_to_lch_hue_hint(&mut pixel, 90.0);
+    assert_eq!(pixel[2], 90.0);
+}
+
+#[test]
+fn hk_terms() {
+    // same sampling as examples/hk_data.rs
+    let samples = 360 * 100;
+    let mean = (0..samples)
+        .map(|n| {
+            let hue = 360.0 / (samples as f32) * (n as f32);
+            (hk_2023_fby(hue) + hk_2023_fr(hue)) * 100.0
+        })
+        .sum::<f32>()
+        / samples as f32;
+    assert!((mean - HIGH2023_MEAN).abs() < 1e-3, "{}", mean);
+
+    for hue in [0.0, 45.0, 90.0, 135.0, 200.0, 270.0, 300.0f64] {
+        let lch = [60.0, 40.0, hue];
+        assert_eq!(hk_high2023(&lch), (hk_2023_fby(hue) + hk_2023_fr(hue)) * 40.0);
+    }
+    assert!((hk_2023_fby(90.0f64) - K_HIGH2022[1] as f64).abs() < 1e-6);
+    assert!((hk_2023_fr(0.0f64) - (K_HIGH2022[2] + K_HIGH2022[3]) as f64).abs() < 1e-6);
+    assert_eq!(hk_2023_fr(180.0f64), 0.0);
+}
+
+#[test]
+fn hk_comp_slice() {
+    let mut pixels: Vec<[f64; 4]> = (0..36).map(|n| [60.0, n as f64 * 3.0, n as f64 * 10.0, 0.5]).collect();
+    let mut reference = pixels.clone();
+    reference.iter_mut().for_each(hk_high2023_comp);
+    hk_high2023_comp_slice(&mut pixels);
+    assert_eq!(pixels, reference);
+
+    let mut flat: Vec<f32> = (0..36).flat_map(|n| [60.0, n as f32 * 3.0, n as f32 * 10.0]).collect();
+    flat.push(-1.0);
+    let mut reference: Vec<[f32; 3]> = flat.chunks_exact(3).map(|c| c.try_into().unwrap()).collect();
+    hk_high2023_comp_slice(&mut reference);
+    // `flat` is valid for `flat.len()` elements
+    assert_eq!(
+        unsafe { hk_high2023_comp_ffi::<f32, 3>(flat.as_mut_ptr(), flat.len()) },
+        0
+    );
+    assert_eq!(flat[..flat.len() - 1], *reference.concat());
+    assert_eq!(flat.last(), Some(&-1.0));
+    assert_eq!(unsafe { hk_high2023_comp_ffi::<f32, 3>(core::ptr::null_mut(), 3) }, 3);
+}
+
+#[test]
+#[cfg(feature = "half")]
+fn half_oklab() {
+    use half::f16;
+    let srgb: Vec<[f32; 3]> = (0..=6)
+        .flat_map(|r| (0..=6).flat_map(move |g| (0..=6).map(move |b| [r, g, b].map(|c| 0.2 + c as f32 / 10.0))))
+        .collect();
+    let mut buffer: Vec<[f16; 3]> = srgb.iter().map(|p| p.map(f16::from_f32)).collect();
+    convert_space_chunked(Space::SRGB, Space::OKLAB, &mut buffer);
+    for (h, p) in buffer.iter().zip(srgb.iter()) {
+        let mut reference = p.map(|c| c as f64);
+        convert_space(Space::SRGB, Space::OKLAB, &mut reference);
+        // f16 storage alone is within 3e-4, the rest is from rounding every op and coefficient
+        let stored = reference.map(|c| f16::from_f64(c).to_f64());
+        pix_cmp(&[stored], &[reference], 3e-4, &[]);
+        pix_cmp(&[h.map(f16::to_f64)], &[reference], 2.5e-3, &[]);
+
+        // which the way back magnifies far more than the storage
+        let mut stored_back = stored;
+        convert_space(Space::OKLAB, Space::SRGB, &mut stored_back);
+        pix_cmp(&[stored_back], &[p.map(|c| c as f64)], 1e-3, &[]);
+    }
+    convert_space_chunked(Space::OKLAB, Space::SRGB, &mut buffer);
+    for (h, p) in buffer.iter().zip(srgb.iter()) {
+        pix_cmp(&[h.map(f16::to_f64)], &[p.map(|c| c as f64)], 2e-2, &[]);
+    }
+
+    let mut pixel = [f16::from_f32(0.5); 4];
+    convert_space(Space::SRGB, Space::OKLCH, &mut pixel);
+    assert_eq!(pixel[3], f16::from_f32(0.5));
+    assert!(pixel[1].to_f32() < 1e-3);
+}
 // ### Single FN Accuracy ### }}}
 
 /// ### Other Tests ### {{{
@@ -358,6 +2180,415 @@ fn alpha_untouch() {
         .for_each(|n| assert_eq!(n.to_bits(), 4.0_f64.to_bits(), "alpha_untouch_sliced"));
 }
 
+#[test]
+fn gamma_alpha() {
+    let original = [0.2, 0.5, 0.8, 0.5f64];
+
+    let mut untouched = original;
+    convert_space_alpha(Space::SRGB, Space::OKLAB, &mut untouched, false);
+    let mut plain = original;
+    convert_space(Space::SRGB, Space::OKLAB, &mut plain);
+    assert_eq!(untouched, plain);
+    assert_eq!(untouched[3].to_bits(), 0.5_f64.to_bits());
+
+    let mut gamma = original;
+    convert_space_alpha(Space::SRGB, Space::OKLAB, &mut gamma, true);
+    assert_eq!(gamma[..3], plain[..3]);
+    assert_eq!(gamma[3], srgb_eotf(0.5));
+    convert_space_alpha(Space::OKLAB, Space::HSV, &mut gamma, true);
+    convert_space_alpha(Space::HSV, Space::SRGB, &mut gamma, true);
+    assert!((gamma[3] - 0.5).abs() < 1e-12);
+
+    // routes that stay on one side of the transfer leave alpha alone
+    let mut linear = [0.2, 0.5, 0.8, 0.5f64];
+    convert_space_alpha(Space::LRGB, Space::CIELCH, &mut linear, true);
+    assert_eq!(linear[3], 0.5);
+}
+
+#[test]
+fn convert_nan_input() {
+    // CSS `none` is NaN, which every entry point carries rather than rejects
+    let (space, none) = str2col::<f32, 3>("oklch(0.6 none 120)").unwrap();
+    let mut pixel = none;
+    convert_space(space, Space::SRGB, &mut pixel);
+    assert!(pixel.iter().all(|c| c.is_nan()));
+    let mut pixels = [none; 2];
+    convert_space_chunked(space, Space::SRGB, &mut pixels);
+    assert!(pixels.iter().flatten().all(|c| c.is_nan()));
+    let mut cast = [[0.0f64; 3]];
+    convert_space_cast(space, Space::SRGB, &[none], &mut cast);
+    assert!(cast[0].iter().all(|c| c.is_nan()));
+    assert!(convert_space_iter(space, Space::SRGB, [none])
+        .flatten()
+        .all(|c| c.is_nan()));
+    assert!(str2space::<f32, 3>("oklch(0.6 none 120)", Space::SRGB)
+        .unwrap()
+        .iter()
+        .all(|c| c.is_nan()));
+
+    // and displays as 0 like CSS
+    let gray = to_display_rgb8([0.6, 0.0, 120.0f32], Space::OKLCH, false);
+    assert_eq!(to_display_rgb8(none, space, false), gray);
+    assert_eq!(to_display_rgb8(none, space, true), gray);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "infinite Oklch pixel")]
+fn convert_inf_input() {
+    convert_space(Space::OKLCH, Space::SRGB, &mut [0.5f32, f32::INFINITY, 120.0]);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "infinite sRGB pixel")]
+fn convert_iter_inf_input() {
+    convert_space_iter(Space::SRGB, Space::OKLAB, [[0.5f32, f32::NEG_INFINITY, 0.0]]).for_each(drop);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "infinite sRGB pixel")]
+fn convert_chunked_inf_input() {
+    convert_space_chunked(
+        Space::SRGB,
+        Space::OKLAB,
+        &mut [[0.5f64; 4], [0.1, f64::INFINITY, 0.0, 1.0]],
+    );
+}
+
+#[test]
+fn convert_nan_alpha() {
+    let mut pixel = [1.0f32, 1.0, 1.0, f32::NAN];
+    convert_space(Space::SRGB, Space::OKLAB, &mut pixel);
+    assert!(pixel[3].is_nan());
+}
+
+#[test]
+fn convert_cast() {
+    let src: Vec<[f32; 4]> = SRGB
+        .iter()
+        .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32, 0.5])
+        .collect();
+    let mut dst = vec![[0.0f64; 4]; src.len() + 1];
+    convert_space_cast(Space::SRGB, Space::OKLAB, &src, &mut dst);
+    let mut reference: Vec<[f64; 3]> = src.iter().map(|p| [p[0] as f64, p[1] as f64, p[2] as f64]).collect();
+    convert_space_chunked(Space::SRGB, Space::OKLAB, &mut reference);
+    let written: Vec<[f64; 3]> = dst[..src.len()].iter().map(|p| [p[0], p[1], p[2]]).collect();
+    pix_cmp(&written, &reference, 1e-12, &[]);
+    assert!(dst[..src.len()].iter().all(|p| p[3] == 0.5));
+    assert_eq!(dst[src.len()], [0.0; 4]);
+
+    let mut short = [[0.0f32; 3]];
+    convert_space_cast(Space::SRGB, Space::LRGB, &[[0.5f64; 3], [1.0; 3]], &mut short);
+    assert!((short[0][0] - 0.21404114).abs() < 1e-6, "{:?}", short);
+}
+
+#[test]
+fn convert_if_needed() {
+    let original = [0.2, 0.5, 0.9f32];
+    let mut pixel = original;
+    assert!(!convert_space_if_needed(Space::SRGB, Space::SRGB, &mut pixel));
+    assert_eq!(pixel, original);
+    assert!(convert_space_if_needed(Space::SRGB, Space::HSV, &mut pixel));
+    let mut reference = original;
+    convert_space(Space::SRGB, Space::HSV, &mut reference);
+    assert_eq!(pixel, reference);
+    for from in Space::ALL {
+        for to in Space::ALL {
+            let mut pixel = [0.3, 0.6, 0.4f64];
+            convert_space(Space::SRGB, *from, &mut pixel);
+            assert_eq!(convert_space_if_needed(*from, *to, &mut pixel), from != to);
+        }
+    }
+}
+
+#[test]
+fn convert_u8_ffi() {
+    let [srgb, hsv, lab] = ["srgb", "hsv", "lab"].map(|s| std::ffi::CString::new(s).unwrap());
+    let bad = std::ffi::CString::new("cmyk").unwrap();
+    // the names are nul terminated and every buffer below is at least `len` long
+    let convert = |from, to, pixels, len, channels| unsafe { convert_space_u8_ffi(from, to, pixels, len, channels) };
+
+    let original: Vec<u8> = vec![255, 0, 0, 51, 102, 153, 7];
+    let mut flat = original.clone();
+    assert_eq!(
+        convert(srgb.as_ptr(), hsv.as_ptr(), flat.as_mut_ptr(), flat.len(), 3),
+        0
+    );
+    assert_eq!(flat, vec![0, 255, 255, 149, 170, 153, 7]);
+    assert_eq!(
+        convert(hsv.as_ptr(), srgb.as_ptr(), flat.as_mut_ptr(), flat.len(), 3),
+        0
+    );
+    // 8 bit hsv can be a step off on the way back
+    assert!(
+        flat.iter().zip(&original).all(|(a, b)| a.abs_diff(*b) <= 1),
+        "{:?}",
+        flat
+    );
+    assert_eq!(flat[6], 7);
+
+    let mut rgba: Vec<u8> = vec![51, 102, 153, 128];
+    assert_eq!(
+        convert(srgb.as_ptr(), hsv.as_ptr(), rgba.as_mut_ptr(), rgba.len(), 4),
+        0
+    );
+    let mut reference = [[51u8, 102, 153, 128]];
+    convert_space_irgb(Space::SRGB, Space::HSV, &mut reference).unwrap();
+    assert_eq!(rgba, reference[0]);
+    assert_eq!(rgba[3], 128);
+
+    let ptr = rgba.as_mut_ptr();
+    assert_eq!(convert(bad.as_ptr(), lab.as_ptr(), ptr, 4, 4), 1);
+    assert_eq!(convert(srgb.as_ptr(), bad.as_ptr(), ptr, 4, 4), 2);
+    assert_eq!(convert(srgb.as_ptr(), lab.as_ptr(), core::ptr::null_mut(), 4, 4), 3);
+    assert_eq!(convert(srgb.as_ptr(), lab.as_ptr(), ptr, 4, 2), 4);
+    assert_eq!(convert(srgb.as_ptr(), lab.as_ptr(), ptr, 4, 5), 4);
+    // 0..=255 can't hold LAB
+    assert_eq!(convert(srgb.as_ptr(), lab.as_ptr(), ptr, 4, 4), 5);
+    assert_eq!(convert(lab.as_ptr(), srgb.as_ptr(), ptr, 4, 4), 5);
+    assert_eq!(rgba, reference[0]);
+    assert!(convert_space_irgb(Space::OKLAB, Space::SRGB, &mut reference).is_err());
+    assert!(convert_space_irgb(Space::SRGB, Space::XYZ, &mut reference).is_err());
+    assert_eq!(rgba, reference[0]);
+}
+
+#[test]
+fn luma_alpha() {
+    let mut pixel = [0.5f64, 0.25];
+    convert_space_luma(Space::SRGB, Space::LRGB, &mut pixel);
+    assert!((pixel[0] - 0.21404114048223255).abs() < 1e-12, "{:?}", pixel);
+    assert_eq!(pixel[1], 0.25);
+    convert_space_luma(Space::LRGB, Space::SRGB, &mut pixel);
+    assert!((pixel[0] - 0.5).abs() < 1e-12, "{:?}", pixel);
+
+    for space in Space::ALL {
+        let mut luma = [0.5f64, 0.75];
+        convert_space_luma(Space::SRGB, *space, &mut luma);
+        let mut reference = [0.5; 3];
+        convert_space(Space::SRGB, *space, &mut reference);
+        assert!(reference.contains(&luma[0]), "{} {:?} {:?}", space, luma, reference);
+        assert_eq!(luma[1], 0.75);
+        convert_space_luma(*space, Space::SRGB, &mut luma);
+        // D65 gray has slightly nonzero Az and Bz
+        assert!((luma[0] - 0.5).abs() < 1e-3, "{} {:?}", space, luma);
+    }
+}
+
+#[test]
+fn conversion_stats() {
+    let mut pixels = SRGB.to_vec();
+    let stats = convert_space_chunked_stats(Space::SRGB, Space::OKLAB, &mut pixels);
+    let mut reference = SRGB.to_vec();
+    convert_space_chunked(Space::SRGB, Space::OKLAB, &mut reference);
+    assert_eq!(pixels, reference);
+    assert_eq!(stats.non_finite, 0);
+    for n in 0..3 {
+        let channel = reference.iter().map(|p| p[n]);
+        assert_eq!(stats.min[n], channel.clone().fold(f64::INFINITY, f64::min));
+        assert_eq!(stats.max[n], channel.clone().fold(f64::NEG_INFINITY, f64::max));
+        let mean = channel.sum::<f64>() / reference.len() as f64;
+        assert!((stats.mean[n] - mean).abs() < 1e-12, "{:?}", stats);
+        assert!(stats.min[n] <= stats.mean[n] && stats.mean[n] <= stats.max[n]);
+    }
+
+    let mut broken = [[0.5f32, 0.5, 0.5, 1.0], [0.2, 0.4, 0.6, f32::NAN]];
+    let stats = convert_space_chunked_stats(Space::SRGB, Space::LRGB, &mut broken);
+    assert_eq!(stats.non_finite, 1);
+    assert_eq!(stats.mean[3], 1.0);
+    let empty = convert_space_chunked_stats::<f32, 3>(Space::SRGB, Space::LRGB, &mut []);
+    assert!(empty.mean.iter().all(|m| m.is_nan()));
+    assert_eq!(empty.min, [f32::INFINITY; 3]);
+}
+
+#[test]
+fn traced() {
+    let mut steps = Vec::new();
+    let mut pixel = [0.8f64, 0.3, 0.2, 0.5];
+    convert_space_traced(Space::SRGB, Space::CIELCH, &mut pixel, &mut |space, values| {
+        steps.push((space, *values))
+    });
+    assert_eq!(
+        steps.iter().map(|s| s.0).collect::<Vec<_>>(),
+        vec![Space::LRGB, Space::XYZ, Space::CIELAB, Space::CIELCH]
+    );
+    let mut reference = [0.8f64, 0.3, 0.2, 0.5];
+    convert_space(Space::SRGB, Space::CIELCH, &mut reference);
+    assert_eq!(pixel, reference);
+    assert_eq!(steps.last().unwrap().1, reference);
+
+    let mut count = 0;
+    convert_space_traced(Space::OKLAB, Space::OKLAB, &mut pixel, &mut |_, _| count += 1);
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn extract_channels() {
+    let red = [1.0f64, 0.0, 0.0];
+    assert!((extract_channel(&red, Space::OKLCH, 2) - 29.23).abs() < 0.01);
+    assert!((extract_channel(&red, Space::OKLAB, 0) - 0.62796).abs() < 1e-4);
+    assert_eq!(extract_channel(&red, Space::SRGB, 0), 1.0);
+    for space in Space::ALL {
+        let mut full = [0.2f32, 0.5, 0.8];
+        convert_space(Space::SRGB, *space, &mut full);
+        for (n, channel) in full.iter().enumerate() {
+            assert_eq!(extract_channel(&[0.2f32, 0.5, 0.8], *space, n), *channel);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn extract_channel_alpha() {
+    extract_channel(&[0.2f32, 0.5, 0.8], Space::OKLAB, 3);
+}
+
+#[test]
+fn luts() {
+    let lut: Vec<[f32; 3]> = generate_lut(Space::SRGB, Space::SRGB, 2);
+    assert_eq!(
+        lut,
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 1.0],
+        ]
+    );
+    assert_eq!(
+        write_cube_lut(&lut[..], Space::SRGB),
+        "LUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 1 1 1\n\
+0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n"
+    );
+
+    let lut: Vec<[f64; 3]> = generate_lut(Space::SRGB, Space::OKLAB, 3);
+    assert_eq!(lut.len(), 27);
+    let mut corner = [1.0, 0.5, 0.0];
+    convert_space(Space::SRGB, Space::OKLAB, &mut corner);
+    // red varies fastest, then green, then blue
+    let (r, g, b) = (2, 1, 0);
+    assert_eq!(lut[r + g * 3 + b * 9], corner);
+    assert!(write_cube_lut(&lut[..], Space::SRGB).starts_with("LUT_3D_SIZE 3\n"));
+}
+
+#[test]
+fn cube_luts() {
+    let identity = CubeLut::parse(&write_cube_lut(
+        &generate_lut::<f32>(Space::SRGB, Space::SRGB, 5)[..],
+        Space::SRGB,
+    ))
+    .unwrap();
+    assert_eq!(identity.size, 5);
+    for pixel in [[0.0f64, 0.0, 0.0], [1.0, 1.0, 1.0], [0.2, 0.5, 0.8], [0.33, 0.91, 0.07]] {
+        let mut mapped = pixel;
+        apply_cube_lut(&mut mapped, &identity);
+        pix_cmp(&[mapped], &[pixel], 1e-6, &[]);
+    }
+
+    // sRGB -> linear, interpolated between samples
+    let lut: Vec<[f32; 3]> = generate_lut(Space::SRGB, Space::LRGB, 33);
+    let linear = CubeLut::parse(&write_cube_lut(&lut[..], Space::SRGB)).unwrap();
+    let mut pixel = [0.2f32, 0.5, 0.8];
+    apply_cube_lut(&mut pixel, &linear);
+    let mut reference = [0.2f32, 0.5, 0.8];
+    srgb_to_lrgb(&mut reference);
+    assert!(
+        pixel.iter().zip(reference).all(|(a, b)| (a - b).abs() < 1e-3),
+        "{:?}",
+        pixel
+    );
+
+    // custom domain and comments, with out of domain input clamped
+    let text = "# comment\nTITLE \"half\"\nLUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n\
+0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n";
+    let half = CubeLut::parse(text).unwrap();
+    assert_eq!(half.domain_max, [2.0; 3]);
+    let mut pixel = [1.0f32, 0.5, 3.0];
+    apply_cube_lut(&mut pixel, &half);
+    assert_eq!(pixel, [0.5, 0.25, 1.0]);
+
+    assert!(CubeLut::parse("LUT_3D_SIZE 2\n0 0 0\n").is_err());
+    assert!(CubeLut::parse("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n").is_err());
+    assert!(CubeLut::parse("LUT_3D_SIZE 2\n0 0 x\n").is_err());
+    assert!(CubeLut::parse("LUT_3D_SIZE 1\n0 0 0\n").is_err());
+
+    // hand built LUTs can skip parse validation
+    for size in [0, 1] {
+        let tiny = CubeLut {
+            size,
+            domain_min: [0.0; 3],
+            domain_max: [1.0; 3],
+            table: vec![[0.5; 3]; size],
+        };
+        let mut pixel = [0.2f32, 0.5, 0.8];
+        apply_cube_lut(&mut pixel, &tiny);
+        assert_eq!(pixel, [0.2, 0.5, 0.8]);
+    }
+}
+
+#[test]
+fn single_transfer() {
+    for from in Space::ALL {
+        for to in Space::ALL {
+            let mut route = vec![*from];
+            convert_space_traced(*from, *to, &mut [0.5f32; 3], &mut |space, _| route.push(space));
+            let mut unique = route.clone();
+            unique.sort_by_key(|s| s.to_string());
+            unique.dedup();
+            assert_eq!(unique.len(), route.len(), "{:?}", route);
+            let transfers = route
+                .windows(2)
+                .filter(|w| matches!(w, [Space::SRGB, Space::LRGB] | [Space::LRGB, Space::SRGB]))
+                .count();
+            assert_eq!(transfers == 0, is_transfer_identity(*from, *to), "{:?}", route);
+            assert!(transfers <= 1, "{:?}", route);
+        }
+    }
+}
+
+#[test]
+fn gamut_roundtrip() {
+    for space in Space::ALL {
+        let error = gamut_roundtrip_max_error::<f64>(*space, 16);
+        let error32 = gamut_roundtrip_max_error::<f32>(*space, 16);
+        assert!(error < 1e-5, "{} {}", space, error);
+        // the PQ function in JzAzBz is rough on f32
+        let tolerance = if [Space::JZAZBZ, Space::JZCZHZ].contains(space) {
+            1e-2
+        } else {
+            1e-4
+        };
+        assert!(error32 < tolerance, "{} {}", space, error32);
+    }
+    assert_eq!(gamut_roundtrip_max_error::<f32>(Space::SRGB, 4), 0.0);
+}
+
+#[test]
+fn roundtrip() {
+    let mut expected = [0.2f64, 0.35, 0.95, 0.5];
+    convert_space(Space::SRGB, Space::CIELCH, &mut expected);
+    let mut pixel = [0.2f64, 0.35, 0.95, 0.5];
+    roundtrip_through(Space::SRGB, Space::CIELCH, &mut pixel, |lch| {
+        assert_eq!(*lch, expected);
+        lch[2] += 360.0;
+    });
+    pix_cmp(&[[pixel[0], pixel[1], pixel[2]]], &[[0.2, 0.35, 0.95]], 1e-6, &[]);
+    assert_eq!(pixel[3], 0.5);
+
+    let mut pixel = [0.2f32, 0.35, 0.95];
+    roundtrip_through(Space::SRGB, Space::OKLCH, &mut pixel, |lch| lch[1] = 0.0);
+    assert!(
+        (pixel[0] - pixel[1]).abs() < 1e-3 && (pixel[1] - pixel[2]).abs() < 1e-3,
+        "{:?}",
+        pixel
+    );
+}
+
 #[test]
 fn sliced() {
     let mut pixel: Vec<f64> = SRGB.iter().fold(Vec::new(), |mut acc, it| {
@@ -396,6 +2627,25 @@ fn sliced_odd() {
     assert_eq!(*pixel.last().unwrap(), 1234.5678);
 }
 
+#[test]
+fn iter() {
+    let converted: Vec<[f64; 3]> = convert_space_iter(Space::SRGB, Space::CIELCH, SRGB.iter().copied()).collect();
+    pix_cmp(&converted, CIELCH, 1e-2, &[]);
+
+    let lazy = (0..=100).map(|n| [n as f32 / 100.0, 0.5, 1.0 - n as f32 / 100.0, 0.25]);
+    let iter = convert_space_iter(Space::SRGB, Space::OKLCH, lazy.clone());
+    assert_eq!(iter.size_hint(), (101, Some(101)));
+    for (converted, mut pixel) in iter.zip(lazy) {
+        convert_space(Space::SRGB, Space::OKLCH, &mut pixel);
+        assert_eq!(converted, pixel);
+    }
+
+    assert_eq!(
+        convert_space_iter(Space::HSV, Space::HSV, [[0.1f32, 0.2, 0.3]]).collect::<Vec<_>>(),
+        vec![[0.1, 0.2, 0.3]]
+    );
+}
+
 #[test]
 fn sliced_smol() {
     let pixels = [1.0, 0.0];
@@ -404,6 +2654,81 @@ fn sliced_smol() {
     assert_eq!(pixels, smol);
 }
 
+#[test]
+fn padded() {
+    let colors = [[0.2, 0.5, 0.9f32], [1.0, 0.0, 0.3], [0.6, 0.6, 0.6]];
+    let mut buffer: Vec<f32> = colors.iter().flat_map(|c| [c[0], c[1], c[2], -7.0]).collect();
+    buffer.pop();
+    convert_space_padded(Space::SRGB, Space::OKLCH, &mut buffer, 4);
+    for (n, color) in colors.iter().enumerate() {
+        let mut reference = *color;
+        convert_space(Space::SRGB, Space::OKLCH, &mut reference);
+        assert_eq!(buffer[n * 4..n * 4 + 3], reference);
+        if n * 4 + 3 < buffer.len() {
+            assert_eq!(buffer[n * 4 + 3], -7.0, "padding touched");
+        }
+    }
+
+    let mut tight: Vec<f32> = colors.concat();
+    let mut reference = tight.clone();
+    convert_space_padded(Space::SRGB, Space::OKLCH, &mut tight, 3);
+    convert_space_sliced::<_, 3>(Space::SRGB, Space::OKLCH, &mut reference);
+    assert_eq!(tight, reference);
+}
+
+#[test]
+#[should_panic(expected = "stride must be at least 3")]
+fn padded_bad_stride() {
+    convert_space_padded(Space::SRGB, Space::OKLCH, &mut [0.5f32; 4], 2);
+}
+
+#[test]
+fn chunked_limited() {
+    let original: Vec<[f32; 4]> = (0..100)
+        .map(|n| [n as f32 / 100.0, 0.5, 1.0 - n as f32 / 100.0, 0.5])
+        .collect();
+    let mut reference = original.clone();
+    convert_space_chunked(Space::SRGB, Space::OKLCH, &mut reference);
+
+    let mut pixels = original.clone();
+    let mut done = convert_space_chunked_limited(Space::SRGB, Space::OKLCH, &mut pixels, 60);
+    assert_eq!(done, 60);
+    assert_eq!(pixels[..60], reference[..60]);
+    assert_eq!(pixels[60..], original[60..]);
+    done += convert_space_chunked_limited(Space::SRGB, Space::OKLCH, &mut pixels[done..], 60);
+    assert_eq!(done, 100);
+    assert_eq!(pixels, reference);
+    assert_eq!(
+        convert_space_chunked_limited(Space::SRGB, Space::OKLCH, &mut pixels[done..], 60),
+        0
+    );
+}
+
+#[test]
+fn chunked_fused_srgb() {
+    let original: Vec<[f64; 4]> = (0..64)
+        .map(|n| [n as f64 / 63.0, (n % 8) as f64 / 7.0, 1.0 - n as f64 / 63.0, 0.5])
+        .collect();
+    for space in [
+        Space::XYZ,
+        Space::CIELAB,
+        Space::CIELCH,
+        Space::OKLAB,
+        Space::OKLCH,
+        Space::JZAZBZ,
+        Space::JZCZHZ,
+    ] {
+        let mut fused = original.clone();
+        convert_space_chunked(Space::SRGB, space, &mut fused);
+        let mut stepped = original.clone();
+        stepped.iter_mut().for_each(|pixel| {
+            srgb_to_lrgb(pixel);
+            convert_space(Space::LRGB, space, pixel)
+        });
+        assert_eq!(fused, stepped, "{}", space);
+    }
+}
+
 #[test]
 fn interweave() {
     let srgb: Vec<[f32; 3]> = SRGB.iter().map(|p| p.map(|c| c as f32)).collect();
@@ -426,6 +2751,26 @@ fn interweave() {
     assert_eq!(slice.as_slice(), weave(deinterleaved).as_ref())
 }
 
+#[test]
+fn swizzles() {
+    let rgba = [0.1f32, 0.2, 0.3, 0.4];
+    let mut pixel = rgba;
+    rgba_to_bgra(&mut pixel);
+    assert_eq!(pixel, [0.3, 0.2, 0.1, 0.4]);
+    bgra_to_rgba(&mut pixel);
+    assert_eq!(pixel, rgba);
+
+    let mut pixel = [1u8, 2, 3];
+    swizzle(&mut pixel, [1, 1, 0]);
+    assert_eq!(pixel, [2, 2, 1]);
+}
+
+#[test]
+#[should_panic]
+fn swizzle_bounds() {
+    swizzle(&mut [1u8, 2, 3], [0, 1, 3]);
+}
+
 #[test]
 fn nan_checks() {
     let fns_f64: &[(&'static str, fn(&mut [f64; 3]))] = &[
@@ -441,6 +2786,16 @@ fn nan_checks() {
         ("lch_to_lab", lch_to_lab),
         ("xyz_to_oklab", xyz_to_oklab),
         ("oklab_to_xyz", oklab_to_xyz),
+        ("oklab_to_oklrab", oklab_to_oklrab),
+        ("oklrab_to_oklab", oklrab_to_oklab),
+        ("lrgb_to_oklab", lrgb_to_oklab),
+        ("oklab_to_lrgb", oklab_to_lrgb),
+        ("lrgb_to_lp3", lrgb_to_lp3),
+        ("lp3_to_lrgb", lp3_to_lrgb),
+        ("lrgb_to_lrec2020", lrgb_to_lrec2020),
+        ("lrec2020_to_lrgb", lrec2020_to_lrgb),
+        ("lp3_to_lrec2020", lp3_to_lrec2020),
+        ("lrec2020_to_lp3", lrec2020_to_lp3),
         ("xyz_to_jzazbz", xyz_to_jzazbz),
         ("jzazbz_to_xyz", jzazbz_to_xyz),
         ("_lrgb_to_ictcp", _lrgb_to_ictcp),
@@ -459,6 +2814,16 @@ fn nan_checks() {
         ("lch_to_lab", lch_to_lab),
         ("xyz_to_oklab", xyz_to_oklab),
         ("oklab_to_xyz", oklab_to_xyz),
+        ("oklab_to_oklrab", oklab_to_oklrab),
+        ("oklrab_to_oklab", oklrab_to_oklab),
+        ("lrgb_to_oklab", lrgb_to_oklab),
+        ("oklab_to_lrgb", oklab_to_lrgb),
+        ("lrgb_to_lp3", lrgb_to_lp3),
+        ("lp3_to_lrgb", lp3_to_lrgb),
+        ("lrgb_to_lrec2020", lrgb_to_lrec2020),
+        ("lrec2020_to_lrgb", lrec2020_to_lrgb),
+        ("lp3_to_lrec2020", lp3_to_lrec2020),
+        ("lrec2020_to_lp3", lrec2020_to_lp3),
         // fails hard in the PQ function with (N/D)^P
         //("xyz_to_jzazbz", xyz_to_jzazbz),
         ("jzazbz_to_xyz", jzazbz_to_xyz),
@@ -497,23 +2862,300 @@ fn hue_wrap() {
                 // lch
                 let mut pixel = [a, b, c];
                 convert_space(Space::SRGB, Space::CIELCH, &mut pixel);
-                assert!(pixel[2] <= 360.0, "lch H was {}", pixel[2]);
+                assert!(pixel[2] < 360.0, "lch H was {}", pixel[2]);
                 assert!(pixel[2] >= 0.0, "lch H was {}", pixel[2]);
                 // hsv
                 let mut pixel = [a, b, c];
                 convert_space(Space::SRGB, Space::HSV, &mut pixel);
-                assert!(pixel[0] <= 1.0, "hsv H was {}", pixel[0]);
+                assert!(pixel[0] < 1.0, "hsv H was {}", pixel[0]);
                 assert!(pixel[0] >= 0.0, "hsv H was {}", pixel[0]);
             }
         }
     }
 }
 
+#[test]
+fn white_points() {
+    for (label, white) in [
+        ("D65", D65),
+        ("D50", D50),
+        ("D55", D55),
+        ("D75", D75),
+        ("STD_A", STD_A),
+        ("STD_E", STD_E),
+    ] {
+        assert_eq!(white[1], 1.0, "{} Y not normalized", label);
+        assert!(white.iter().all(|c| c.is_finite() && *c > 0.0), "{} {:?}", label, white);
+    }
+}
+
+#[test]
+fn white_point_lab() {
+    // D65 mid gray through sRGB is only neutral against D65
+    let mut d65 = [0.5f64; 3];
+    convert_space_wp(Space::SRGB, Space::CIELAB, &mut d65, Illuminant::D65);
+    let mut d50 = [0.5f64; 3];
+    convert_space_wp(Space::SRGB, Space::CIELAB, &mut d50, Illuminant::D50);
+    let mut reference = [0.5f64; 3];
+    convert_space(Space::SRGB, Space::CIELAB, &mut reference);
+    pix_cmp(&[d65], &[reference], 1e-8, &[]);
+    assert!((d65[0] - d50[0]).abs() < 1e-8, "{:?} {:?}", d65, d50);
+    assert!(d65[1].abs() < 1e-2 && d65[2].abs() < 1e-2, "{:?}", d65);
+    assert!(d50[1].abs() > 1.0 && d50[2].abs() > 1.0, "{:?}", d50);
+
+    // D50 mid gray in XYZ is neutral against D50
+    let gray = D50.map(|c| c as f64 * 0.2);
+    let mut lch = gray;
+    convert_space_wp(Space::XYZ, Space::CIELCH, &mut lch, Illuminant::D50);
+    assert!(lch[1] < 1e-3, "{:?}", lch);
+    let mut lch65 = gray;
+    convert_space_wp(Space::XYZ, Space::CIELCH, &mut lch65, Illuminant::D65);
+    assert!(lch65[1] > 1.0, "{:?}", lch65);
+    assert!((lch[0] - lch65[0]).abs() < 1e-8);
+
+    // inversion
+    convert_space_wp(Space::CIELCH, Space::XYZ, &mut lch, Illuminant::D50);
+    pix_cmp(&[lch], &[gray], 1e-6, &[]);
+    let mut srgb = d50;
+    convert_space_wp(Space::CIELAB, Space::SRGB, &mut srgb, Illuminant::D50);
+    pix_cmp(&[srgb], &[[0.5; 3]], 1e-6, &[]);
+}
+
+#[test]
+fn white_balance() {
+    let mut pixels: Vec<[f64; 3]> = (0..=32)
+        .map(|n| {
+            let v = n as f64 / 32.0;
+            [v * 0.8 + 0.15, v * 0.85 + 0.05, v * 0.7]
+        })
+        .collect();
+    let mean = |pixels: &[[f64; 3]]| {
+        let mut lab = pixels.to_vec();
+        convert_space_chunked(Space::SRGB, Space::CIELAB, &mut lab);
+        let n = lab.len() as f64;
+        lab.iter().fold([0.0; 3], |acc, p| {
+            [acc[0] + p[0] / n, acc[1] + p[1] / n, acc[2] + p[2] / n]
+        })
+    };
+    let before = mean(&pixels);
+    assert!(before[1].abs() > 1.0 && before[2].abs() > 5.0, "{:?}", before);
+    auto_white_balance(&mut pixels);
+    let after = mean(&pixels);
+    assert!(after[1].abs() < 1e-4 && after[2].abs() < 1e-4, "{:?}", after);
+    assert!((after[0] - before[0]).abs() < 1e-4);
+
+    auto_white_balance::<f32>(&mut []);
+}
+
+#[test]
+fn blackbody() {
+    let white: [f64; 3] = white_point_srgb(6500.0);
+    assert!(white.iter().all(|c| *c > 0.95 && *c <= 1.0), "{:?}", white);
+
+    let warm: [f32; 3] = white_point_srgb(2700.0);
+    assert!(warm.iter().all(|c| *c >= 0.0 && *c <= 1.0), "{:?}", warm);
+    assert!(warm[0] - warm[2] > 0.5, "{:?}", warm);
+
+    let cool: [f32; 3] = white_point_srgb(10000.0);
+    assert!(cool[2] > cool[0], "{:?}", cool);
+
+    // locus should sit near D65 at ~6504K
+    let xyz: [f64; 3] = blackbody_xyz(6504.0);
+    assert_eq!(xyz[1], 1.0);
+    let d65 = D65.map(|c| c as f64);
+    assert!(
+        (xyz[0] - d65[0]).abs() < 0.02 && (xyz[2] - d65[2]).abs() < 0.05,
+        "{:?}",
+        xyz
+    );
+
+    // clamped
+    assert_eq!(blackbody_xyz(100.0f32), blackbody_xyz(1667.0f32));
+    assert_eq!(blackbody_xyz(1e6f32), blackbody_xyz(25000.0f32));
+}
+
+#[test]
+fn warmth() {
+    let gray = [0.5f64; 3];
+    let mut neutral = gray;
+    apply_warmth(&mut neutral, 6500.0);
+    pix_cmp(&[neutral], &[gray], 1e-9, &[]);
+
+    let mut warm = gray;
+    apply_warmth(&mut warm, 2700.0);
+    assert!(warm[0] > warm[1] && warm[1] > warm[2], "{:?}", warm);
+    let mut cool = gray;
+    apply_warmth(&mut cool, 10000.0);
+    assert!(cool[2] > cool[0], "{:?}", cool);
+
+    let mut white = [1.0f32; 3];
+    apply_warmth(&mut white, 1900.0);
+    assert!(white.iter().all(|c| (0.0..=1.0).contains(c)), "{:?}", white);
+    let mut black = [0.0f32; 3];
+    apply_warmth(&mut black, 3000.0);
+    assert_eq!(black, [0.0; 3]);
+}
+
+#[test]
+fn cam16() {
+    // Reference values calculated independently using the same viewing conditions
+    let xyz: &[[f64; 3]] = &[
+        [0.9504559270516716, 1.0, 1.0890577507598784],
+        [0.1901, 0.2, 0.2178],
+        [0.4124, 0.2126, 0.0193],
+        [0.1805, 0.0722, 0.9505],
+        [0.05, 0.1, 0.02],
+    ];
+    let jmh: &[[f64; 3]] = &[
+        [100.0, 2.236898445770788, 209.53333446353292],
+        [41.26547783627703, 1.4297051796028266, 209.575826456374],
+        [46.02203591800327, 81.2829482099639, 27.390639847223447],
+        [25.068168770723343, 62.44129338259745, 282.7641934572219],
+        [26.72114900402114, 43.56921667590505, 143.8837710547818],
+    ];
+    let result: Vec<[f64; 3]> = xyz.iter().map(cam16_jmh).collect();
+    pix_cmp(&result, jmh, 1e-3, &[]);
+    let result32: Vec<[f64; 3]> = xyz
+        .iter()
+        .map(|p| cam16_jmh(&p.map(|c| c as f32)).map(|c| c as f64))
+        .collect();
+    pix_cmp(&result32, jmh, 1e-2, &[]);
+    assert_eq!(cam16_jmh(&[0.0f32; 3])[0], 0.0);
+}
+
+#[test]
+fn hue_normalize() {
+    for space in Space::UCS_POLAR {
+        let mut pixel = [0.5f32, 0.1, 400.0];
+        space.normalize_hue(&mut pixel);
+        assert_eq!(pixel, [0.5, 0.1, 40.0]);
+        let mut pixel = [0.5f64, 0.1, -30.0, 1.0];
+        space.normalize_hue(&mut pixel);
+        assert_eq!(pixel, [0.5, 0.1, 330.0, 1.0]);
+    }
+
+    let mut pixel = [1.25f32, 0.5, 0.5];
+    Space::HSV.normalize_hue(&mut pixel);
+    assert_eq!(pixel, [0.25, 0.5, 0.5]);
+    let mut pixel = [-0.25f32, 0.5, 0.5];
+    Space::HSV.normalize_hue(&mut pixel);
+    assert_eq!(pixel, [0.75, 0.5, 0.5]);
+
+    let mut pixel = [0.5f32, 400.0, -30.0];
+    Space::OKLAB.normalize_hue(&mut pixel);
+    assert_eq!(pixel, [0.5, 400.0, -30.0]);
+}
+
+#[test]
+fn hue_half_open() {
+    // atan2 of a tiny negative b is a tiny negative angle, which plain rem_euclid rounds up to 360
+    for b in [-1e-300, -1e-20, -0.0, 0.0] {
+        let mut pixel = [50.0, 10.0, b];
+        lab_to_lch(&mut pixel);
+        assert!((0.0..360.0).contains(&pixel[2]), "{} {}", b, pixel[2]);
+        let mut pixel = [50.0f32, 10.0, b as f32];
+        lab_to_lch(&mut pixel);
+        assert!((0.0..360.0).contains(&pixel[2]), "{} {}", b, pixel[2]);
+    }
+    let mut pixel = [0.5, 0.2, -1e-20];
+    Space::OKLCH.normalize_hue(&mut pixel);
+    assert_eq!(pixel[2], 0.0);
+    let mut pixel = [-1e-20, 0.5, 0.5];
+    Space::HSV.normalize_hue(&mut pixel);
+    assert_eq!(pixel[0], 0.0);
+    let mut pixel = [0.5, 0.2, 360.0];
+    Space::CIELCH.normalize_hue(&mut pixel);
+    assert_eq!(pixel[2], 0.0);
+
+    assert!(wrapped_hue(350.0, 10.0));
+    assert!(wrapped_hue(10.0, 350.0));
+    assert!(wrapped_hue(359.0, 0.0));
+    assert!(!wrapped_hue(0.0, 1.0));
+    assert!(!wrapped_hue(10.0, 170.0f32));
+    assert!(!wrapped_hue(100.0, 280.0));
+    assert!(wrapped_hue(280.0, 100.0));
+    assert!(wrapped_hue(-10.0, 10.0));
+    assert!(!wrapped_hue(200.0, 200.0));
+}
+
 #[test]
 fn space_strings() {
     for space in Space::ALL {
-        assert_eq!(Ok(*space), Space::try_from(space.to_string().as_str()))
+        assert_eq!(Ok(*space), Space::try_from(space.to_string().as_str()));
+        assert_eq!(Some(*space), Space::from_str_fuzzy(space.to_string().as_str()))
+    }
+}
+
+#[test]
+fn space_strings_fuzzy() {
+    for (input, space) in [
+        ("cie-lab", Space::CIELAB),
+        ("CIE_LCh", Space::CIELCH),
+        ("L*a*b*", Space::CIELAB),
+        ("ok_lch", Space::OKLCH),
+        ("Ok-Lab", Space::OKLAB),
+        ("linear rgb", Space::LRGB),
+        ("Linear", Space::LRGB),
+        ("srgb-linear", Space::LRGB),
+        ("  sRGB\t", Space::SRGB),
+        ("HSB", Space::HSV),
+        ("xyz-d65", Space::XYZ),
+        ("jab", Space::JZAZBZ),
+        ("Jz Cz Hz", Space::JZCZHZ),
+    ] {
+        assert_eq!(Space::from_str_fuzzy(input), Some(space), "{}", input);
     }
+    // strict parsing is unchanged
+    assert_eq!(Space::try_from("cie-lab"), Err(()));
+    assert_eq!(Space::try_from("linear"), Err(()));
+    assert_eq!(Space::from_str_fuzzy("lab lab"), None);
+    assert_eq!(Space::from_str_fuzzy(""), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn color_value_serde() {
+    let color = ColorValue {
+        space: Space::OKLCH,
+        values: [0.6, 0.1, 120.0],
+    };
+    let json = serde_json::to_string(&color).unwrap();
+    assert_eq!(json, r#"{"space":"oklch","values":[0.6,0.1,120.0]}"#);
+    assert_eq!(serde_json::from_str::<ColorValue<3>>(&json).unwrap(), color);
+
+    let alpha = ColorValue {
+        space: Space::CIELAB,
+        values: [50.0, -20.0, 30.0, 0.5],
+    };
+    let json = serde_json::to_string(&alpha).unwrap();
+    assert_eq!(serde_json::from_str::<ColorValue<4>>(&json).unwrap(), alpha);
+    assert!(serde_json::from_str::<ColorValue<3>>(&json).is_err());
+    assert!(serde_json::from_str::<ColorValue<3>>(r#"{"space":"cmyk","values":[0,0,0]}"#).is_err());
+
+    let srgb = color.to_space(Space::SRGB).to_space(Space::OKLCH);
+    assert_eq!(srgb.space, Space::OKLCH);
+    assert!(srgb.values.iter().zip(color.values).all(|(a, b)| (a - b).abs() < 1e-3));
+
+    // missing components carry through like any other conversion
+    let (space, values) = str2col::<f32, 3>("oklch(0.6 none 120)").unwrap();
+    let missing = ColorValue { space, values }.to_space(Space::SRGB);
+    assert!(missing.values.iter().all(|c| c.is_nan()));
+}
+
+#[cfg(feature = "bench")]
+#[test]
+fn bench_convert_smoke() {
+    let elapsed = bench_convert::<f32, 4>(Space::SRGB, Space::CIELCH, 4096);
+    assert!(elapsed > std::time::Duration::ZERO);
+    bench_convert::<f64, 3>(Space::JZCZHZ, Space::HSV, 0);
+}
+
+#[cfg(feature = "bench")]
+#[test]
+fn throughput_smoke() {
+    let mpps = throughput_mpps(Space::SRGB, Space::OKLAB);
+    assert!(mpps.is_finite() && mpps > 0.0, "{}", mpps);
+    assert!(throughput_mpps(Space::LRGB, Space::LRGB).is_finite());
 }
 
 /// ### Other Tests ### }}}
@@ -629,6 +3271,20 @@ fn str2col_hex() {
     assert_eq!(str2col(HEX), Some((Space::SRGB, irgb_to_srgb::<f32, 3>(IRGB))))
 }
 
+#[test]
+fn str2col_hex_alpha() {
+    let (space, rgb) = str2col::<f32, 4>("#FF0000").unwrap();
+    assert_eq!(space, Space::SRGB);
+    assert_eq!(rgb[..3], [1.0, 0.0, 0.0]);
+    assert!(rgb[3].is_nan());
+    assert!(str2col::<f32, 4>("srgb 1 0 0").unwrap().1[3].is_nan());
+    assert_eq!(
+        str2col::<f32, 4>("#FF000080"),
+        Some((Space::SRGB, [1.0, 0.0, 0.0, 128.0 / 255.0]))
+    );
+    assert_eq!(str2col::<f32, 3>("#FF000080"), Some((Space::SRGB, [1.0, 0.0, 0.0])));
+}
+
 #[test]
 fn str2col_perc100() {
     assert_eq!(
@@ -724,6 +3380,65 @@ fn str2col_alpha() {
     assert_eq!(will_nan, (Space::SRGB, [0f32, 0.5, 0.75, 0.12345]));
 }
 
+#[test]
+fn str2col_missing() {
+    let (space, col) = str2col::<f32, 3>("oklch(0.6 none 120)").unwrap();
+    assert_eq!(space, Space::OKLCH);
+    assert_eq!(col[0], 0.6);
+    assert!(col[1].is_nan());
+    assert_eq!(col[2], 120.0);
+    assert!(str2col::<f32, 4>("NaN 0.5 0.5 none")
+        .unwrap()
+        .1
+        .iter()
+        .enumerate()
+        .all(|(n, c)| c.is_nan() == (n % 3 == 0)));
+    assert_eq!(str2col::<f32, 3>("0.2 0.5"), None);
+    assert_eq!(str2col::<f32, 3>("0.2 0.5 inf"), None);
+    assert!(str2space::<f32, 3>("oklch(0.6 0.1 none)", Space::SRGB)
+        .unwrap()
+        .iter()
+        .all(|c| c.is_nan()));
+}
+
+#[test]
+fn palette_files() {
+    let gpl = "GIMP Palette
+Name: Sample
+Columns: 3
+# a comment
+255   0   0\tRed
+  0 128 255\tAzure Blue
+# 300 0 0 out of range
+300   0   0\tBroken
+ 16  16  16
+";
+    assert_eq!(parse_gpl(gpl), vec![[255, 0, 0], [0, 128, 255], [16, 16, 16]]);
+
+    let pal = "JASC-PAL\r
+0100\r
+3\r
+255 0 0\r
+0 128 255\r
+16 16 16\r
+1 2 3\r
+";
+    assert_eq!(parse_jasc_pal(pal), vec![[255, 0, 0], [0, 128, 255], [16, 16, 16]]);
+    assert!(parse_jasc_pal("0100\n1\n255 0 0").is_empty());
+    assert!(parse_gpl("").is_empty());
+}
+
+#[test]
+fn str2col_alpha_perc() {
+    for space in [
+        "srgb", "hsv", "lrgb", "xyz", "lab", "lch", "oklab", "oklch", "jzazbz", "jzczhz",
+    ] {
+        let (_, col) = str2col::<f32, 4>(&format!("{} 50% 50% 50% 50%", space)).unwrap();
+        assert_eq!(col[3], 0.5, "{}", space);
+    }
+    assert_eq!(str2col::<f32, 4>("oklch 50% 50% 50% 0%").unwrap().1[3], 0.0);
+}
+
 #[test]
 fn str2space_base() {
     let pix: [f64; 3] =
@@ -738,4 +3453,90 @@ fn str2space_hex() {
     let reference = [0.62792590, 0.25768453, 29.22319405];
     pix_cmp(&[pix], &[reference], 1e-3, &[]);
 }
+
+#[test]
+fn css_color_fn() {
+    assert_eq!(
+        str2col("color(srgb 0.2 0.5 0.6)"),
+        Some((Space::SRGB, [0.2f32, 0.5, 0.6]))
+    );
+    assert_eq!(
+        str2col("COLOR(srgb-linear 50% 0.5 0.5)"),
+        Some((Space::LRGB, [0.5f32, 0.5, 0.5]))
+    );
+    assert_eq!(
+        str2col("color(xyz 0.1 0.2 0.3)"),
+        Some((Space::XYZ, [0.1f32, 0.2, 0.3]))
+    );
+    assert_eq!(
+        str2col("color(xyz-d65 0.1 0.2 0.3)"),
+        Some((Space::XYZ, [0.1f32, 0.2, 0.3]))
+    );
+
+    // P3 red is outside of sRGB, so compare in linear light
+    let (space, p3_red) = str2col::<f64, 3>("color(display-p3 1 0 0)").unwrap();
+    assert_eq!(space, Space::LRGB);
+    let mut reference = [1.0, 0.0, 0.0];
+    lp3_to_lrgb(&mut reference);
+    pix_cmp(&[p3_red], &[reference], 1e-9, &[]);
+    assert!(p3_red[0] > 1.0 && p3_red[1] < 0.0);
+
+    // every space agrees on white, save for rounding
+    for s in ["color(display-p3 1 1 1)", "color(rec2020 1 1 1)", "color(srgb 1 1 1)"] {
+        let white = str2space::<f64, 3>(s, Space::LRGB).unwrap();
+        pix_cmp(&[white], &[[1.0; 3]], 1e-5, &[]);
+    }
+    let d50 = str2space::<f64, 3>(&format!("color(xyz-d50 {} {} {})", D50[0], D50[1], D50[2]), Space::XYZ).unwrap();
+    pix_cmp(&[d50], &[D65.map(|c| c as f64)], 1e-6, &[]);
+
+    // grays stay gray through either matrix, leaving only the transfer
+    let rec_gray = str2space::<f64, 3>("color(rec2020 0.5 0.5 0.5)", Space::LRGB).unwrap();
+    let rec_lin = ((0.5 + 0.09929682680944) / 1.09929682680944f64).powf(1.0 / 0.45);
+    pix_cmp(&[rec_gray], &[[rec_lin; 3]], 1e-6, &[]);
+    // negative components mirror the transfer function
+    let negative = str2space::<f64, 3>("color(display-p3 -0.5 -0.5 -0.5)", Space::LRGB).unwrap();
+    pix_cmp(&[negative], &[[-srgb_eotf(0.5f64); 3]], 1e-6, &[]);
+    let negative = str2space::<f64, 3>("color(rec2020 -0.5 -0.5 -0.5)", Space::LRGB).unwrap();
+    pix_cmp(&[negative], &[[-rec_lin; 3]], 1e-6, &[]);
+
+    let (_, alpha) = str2col::<f32, 4>("color(display-p3 0.5 0.5 0.5 / 0.25)").unwrap();
+    assert_eq!(alpha[3], 0.25);
+    assert!(str2col::<f32, 3>("color(display-p3 none 0 0)").unwrap().1[0].is_nan());
+
+    for bad in [
+        "color(prophoto-rgb 1 0 0)",
+        "color(srgb lab 1 0 0)",
+        "color(srgb #fff)",
+        "color(srgb 1 0)",
+        "color()",
+    ] {
+        assert_eq!(str2col::<f32, 3>(bad), None, "{}", bad);
+    }
+}
+
+#[test]
+fn css_p3_roundtrip() {
+    let srgb = [0.9, 0.3, 0.1, 0.5f64];
+    let css = srgb_to_css_p3(srgb);
+    assert!(
+        css.starts_with("color(display-p3 ") && css.ends_with(" / 0.5)"),
+        "{}",
+        css
+    );
+    let back = str2space::<f64, 4>(&css, Space::SRGB).unwrap();
+    pix_cmp(
+        &[[back[0], back[1], back[2]]],
+        &[[srgb[0], srgb[1], srgb[2]]],
+        1e-6,
+        &[],
+    );
+    assert_eq!(back[3], 0.5);
+    // colors outside of P3 write negative components
+    let wide = [0.0, -0.2, 1.2f64];
+    let css = srgb_to_css_p3(wide);
+    assert!(css.contains(" -"), "{}", css);
+    let back = str2space::<f64, 3>(&css, Space::SRGB).unwrap();
+    pix_cmp(&[back], &[wide], 1e-6, &[]);
+    assert!(!srgb_to_css_p3([0.5f32, 0.5, 0.5, f32::NAN]).contains('/'));
+}
 // ### Str2Col ### }}}